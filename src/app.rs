@@ -1,34 +1,233 @@
-use crate::dist::{DAYS, Stats, gen_random_dist, plot_data};
+use crate::dist::{
+    DEFAULT_YEARS, ErrorModel, FanChart, Histogram, SimConfig, Stats, gen_fan_chart, gen_round,
+    plot_data, return_histogram, rolling_sharpe, rolling_windows, underwater_data,
+};
+use crate::leaderboard::{self, Entry};
 use compact_str::CompactString;
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppMode {
     Display,
-    Guessing(Guess),
+    Guessing(Box<Guess>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Guess {
     pub state: GuessState,
     pub target: GuessTarget,
+    pub error_model: ErrorModel,
+    /// Hit window in standard errors of the target either side of it.
+    pub tolerance: f64,
     pub current_guess: CompactString,
     pub score: u32,
+    pub scoring: Scoring,
+    /// Running total of continuous points across rounds.
+    pub points: f64,
+    pub last_points: f64,
     pub last_guess: Option<f64>,
     pub guess_was_correct: bool,
+    /// Session length, or `None` to keep playing until quitting.
+    pub rounds: Option<usize>,
+    pub history: Vec<RoundResult>,
+    pub started: Instant,
+    /// Time taken over the whole session, set once it finishes.
+    pub elapsed: Option<Duration>,
+    pub player: CompactString,
+    pub difficulty: Difficulty,
+    /// Description of the game settings recorded with the session on the leaderboard.
+    pub settings: CompactString,
+    /// Date of the daily challenge being played, if any.
+    pub daily: Option<CompactString>,
+    /// Whether the session uses the unmodified difficulty preset and so goes on the leaderboard.
+    pub ranked: bool,
+}
+
+/// Outcome of a single submitted guess.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundResult {
+    pub target: GuessTarget,
+    pub guess: f64,
+    pub value: f64,
+    /// Standard error of the target, which standardizes the miss.
+    pub error: f64,
+    pub correct: bool,
+    pub points: f64,
+}
+
+impl RoundResult {
+    pub fn standardized_error(&self) -> f64 {
+        (self.guess - self.value).abs() / self.error
+    }
+}
+
+/// Summary of a finished session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scorecard {
+    pub rounds: usize,
+    pub hits: usize,
+    pub points: f64,
+    pub mean_abs_error: f64,
+    pub mean_standardized_error: f64,
+    /// Mean signed error, positive when the player overestimates.
+    pub bias: f64,
+    /// Indices into the history of the rounds with the smallest and largest standardized error.
+    pub best: usize,
+    pub worst: usize,
+}
+
+impl Guess {
+    pub fn scorecard(&self) -> Option<Scorecard> {
+        if self.history.is_empty() {
+            return None;
+        }
+        let n = self.history.len() as f64;
+        let mean = |f: fn(&RoundResult) -> f64| self.history.iter().map(f).sum::<f64>() / n;
+        let by_error = |a: &(usize, &RoundResult), b: &(usize, &RoundResult)| {
+            a.1.standardized_error()
+                .total_cmp(&b.1.standardized_error())
+        };
+        let rounds = self.history.iter().enumerate();
+
+        Some(Scorecard {
+            rounds: self.history.len(),
+            hits: self.history.iter().filter(|r| r.correct).count(),
+            points: self.history.iter().map(|r| r.points).sum(),
+            mean_abs_error: mean(|r| (r.guess - r.value).abs()),
+            mean_standardized_error: mean(RoundResult::standardized_error),
+            bias: mean(|r| r.guess - r.value),
+            best: rounds.clone().min_by(by_error)?.0,
+            worst: rounds.max_by(by_error)?.0,
+        })
+    }
+
+    /// Spoiler-free daily result to paste into chat, e.g. a 🟩/🟥 grid of hits and misses.
+    pub fn share_text(&self) -> Option<String> {
+        let date = self.daily.as_ref()?;
+        let card = self.scorecard()?;
+        let grid: Vec<String> = self
+            .history
+            .chunks(5)
+            .map(|row| {
+                row.iter()
+                    .map(|round| if round.correct { '🟩' } else { '🟥' })
+                    .collect()
+            })
+            .collect();
+        Some(format!(
+            "Guess the Sharpe {date}: {}/{} hits, {:.0} points\n{}\nMean error {:.2}σ",
+            card.hits,
+            card.rounds,
+            card.points,
+            grid.join("\n"),
+            card.mean_standardized_error
+        ))
+    }
+
+    pub fn is_last_round(&self) -> bool {
+        self.rounds
+            .is_some_and(|rounds| self.history.len() >= rounds)
+    }
+}
+
+/// Presets for the tolerance, prior range, horizon and return model.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Difficulty {
+    /// Wide tolerance and long, Gaussian track records
+    Easy,
+    /// The classic game: 2 years of Gaussian returns with Sharpe between -3 and 3
+    Normal,
+    /// One year of fat-tailed returns with Sharpe between -2 and 2
+    Hard,
+    /// Six months of GARCH returns with Sharpe between -1 and 1
+    Expert,
+}
+
+impl Difficulty {
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Expert => "Expert",
+        }
+    }
+
+    /// Tolerance in standard errors, e.g. 0.12 standard errors catches about 10% of the
+    /// sampling distribution.
+    pub fn tolerance(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.25,
+            Difficulty::Normal => 0.12,
+            Difficulty::Hard => 0.08,
+            Difficulty::Expert => 0.05,
+        }
+    }
+
+    /// Bounds of the uniform prior on the true Sharpe.
+    pub fn prior_range(self) -> (f64, f64) {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => (-3.0, 3.0),
+            Difficulty::Hard => (-2.0, 2.0),
+            Difficulty::Expert => (-1.0, 1.0),
+        }
+    }
+
+    /// Track record length in years.
+    pub fn years(self) -> f64 {
+        match self {
+            Difficulty::Easy => 4.0,
+            Difficulty::Normal => DEFAULT_YEARS,
+            Difficulty::Hard => 1.0,
+            Difficulty::Expert => 0.5,
+        }
+    }
+}
+
+/// How the points for a guess decay with its standardized error z = |guess − target| / error.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Scoring {
+    /// 100 points falling linearly to zero at z = 3
+    Linear,
+    /// 100 · exp(−z²/2), a Gaussian kernel
+    Gaussian,
+    /// 100 points less 50 for each doubling of 1 + z, reaching zero at z = 3
+    Log,
+}
+
+impl Scoring {
+    pub fn points(self, z: f64) -> f64 {
+        if !z.is_finite() {
+            return 0.0;
+        }
+        match self {
+            Scoring::Linear => 100.0 * (1.0 - z / 3.0).max(0.0),
+            Scoring::Gaussian => 100.0 * (-0.5 * z * z).exp(),
+            Scoring::Log => 100.0 * (1.0 - (1.0 + z).log2() / 2.0).max(0.0),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GuessState {
     WaitingForGuess,
     ShowingResult,
+    Finished,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GuessTarget {
     Sample,
     Actual,
+    MaxDrawdown,
+    Sortino,
+    Calmar,
+    Omega,
+    TailRatio,
+    Volatility,
 }
 
 impl GuessTarget {
@@ -36,52 +235,221 @@ impl GuessTarget {
         match self {
             GuessTarget::Sample => "Sample",
             GuessTarget::Actual => "Actual",
+            GuessTarget::MaxDrawdown => "Max Drawdown",
+            GuessTarget::Sortino => "Sortino",
+            GuessTarget::Calmar => "Calmar",
+            GuessTarget::Omega => "Omega",
+            GuessTarget::TailRatio => "Tail Ratio",
+            GuessTarget::Volatility => "Volatility",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            GuessTarget::Sample => GuessTarget::Actual,
+            GuessTarget::Actual => GuessTarget::MaxDrawdown,
+            GuessTarget::MaxDrawdown => GuessTarget::Sortino,
+            GuessTarget::Sortino => GuessTarget::Calmar,
+            GuessTarget::Calmar => GuessTarget::Omega,
+            GuessTarget::Omega => GuessTarget::TailRatio,
+            GuessTarget::TailRatio => GuessTarget::Volatility,
+            GuessTarget::Volatility => GuessTarget::Sample,
+        }
+    }
+
+    /// The value being guessed in this round.
+    pub fn value(self, stats: &Stats) -> f64 {
+        match self {
+            GuessTarget::Sample => stats.sample_sharpe,
+            GuessTarget::Actual => stats.acc_sharpe,
+            GuessTarget::MaxDrawdown => stats.metrics.max_drawdown,
+            GuessTarget::Sortino => stats.metrics.sortino,
+            GuessTarget::Calmar => stats.metrics.calmar,
+            GuessTarget::Omega => stats.metrics.omega,
+            GuessTarget::TailRatio => stats.metrics.tail_ratio,
+            GuessTarget::Volatility => stats.metrics.volatility,
+        }
+    }
+
+    /// One standard deviation of the target's sampling distribution, which scales the tolerance.
+    pub fn error(self, stats: &Stats, error_model: ErrorModel) -> f64 {
+        match self {
+            GuessTarget::Sample | GuessTarget::Actual => stats.error(error_model),
+            GuessTarget::MaxDrawdown => stats.metric_errors.max_drawdown,
+            GuessTarget::Sortino => stats.metric_errors.sortino,
+            GuessTarget::Calmar => stats.metric_errors.calmar,
+            GuessTarget::Omega => stats.metric_errors.omega,
+            GuessTarget::TailRatio => stats.metric_errors.tail_ratio,
+            GuessTarget::Volatility => stats.metric_errors.volatility,
         }
     }
 }
 
+const HISTOGRAM_BINS: usize = 30;
+
 pub struct App {
     pub running: bool,
     pub rng: ChaCha20Rng,
-    pub plot_data: [(f64, f64); DAYS],
+    pub histogram: Histogram,
+    pub show_histogram: bool,
+    pub plot_data: Vec<(f64, f64)>,
+    pub underwater: Vec<(f64, f64)>,
+    /// Rolling Sharpe series paired with their window lengths.
+    pub rolling_sharpe: Vec<(usize, Vec<(f64, f64)>)>,
     pub stats: Stats,
+    /// Alternative paths with the same true Sharpe, only simulated once the answer is shown.
+    pub fan_chart: Option<FanChart>,
     pub mode: AppMode,
+    pub config: SimConfig,
+    /// Fixed seed of a daily challenge, so every player sees the same curves.
+    pub seed: Option<u64>,
+    pub leaderboard_path: Option<PathBuf>,
+    pub show_leaderboard: bool,
+    /// Stored sessions, reloaded each time the leaderboard is opened.
+    pub leaderboard: Vec<Entry>,
+    pub leaderboard_error: Option<String>,
+}
+
+/// Generator for one round of a seeded session, with each round on its own stream.
+fn round_rng(seed: u64, round: u64) -> ChaCha20Rng {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    rng.set_stream(round);
+    rng
+}
+
+/// Rolling Sharpe ratios for each window that fits in the track record.
+fn calc_rolling_sharpe(sample: &[f64], config: &SimConfig) -> Vec<(usize, Vec<(f64, f64)>)> {
+    rolling_windows(config.frequency)
+        .into_iter()
+        .filter(|&window| window >= 2 && window < sample.len())
+        .map(|window| {
+            let data = rolling_sharpe(sample, window, config.frequency.periods_per_year());
+            (window, data)
+        })
+        .collect()
 }
 
 impl App {
-    pub fn new(mode: AppMode) -> Self {
-        let mut rng = ChaCha20Rng::from_os_rng();
-        let (sample, stats) = gen_random_dist(&mut rng);
+    pub fn new(mode: AppMode, config: SimConfig, seed: Option<u64>) -> Self {
+        let mut rng = match seed {
+            Some(seed) => round_rng(seed, 0),
+            None => ChaCha20Rng::from_os_rng(),
+        };
+        let (sample, stats) = gen_round(&config, &mut rng);
 
         let plot_data = plot_data(&sample);
+        let underwater = underwater_data(&plot_data);
+        let rolling_sharpe = calc_rolling_sharpe(&sample, &config);
+        let histogram = return_histogram(&sample, HISTOGRAM_BINS);
+        let fan_chart =
+            (mode == AppMode::Display).then(|| gen_fan_chart(stats.acc_sharpe, &config, &mut rng));
 
         Self {
             running: true,
             rng,
+            histogram,
+            show_histogram: false,
             plot_data,
+            underwater,
+            rolling_sharpe,
             stats,
+            fan_chart,
             mode,
+            config,
+            seed,
+            leaderboard_path: leaderboard::default_path(),
+            show_leaderboard: false,
+            leaderboard: Vec::new(),
+            leaderboard_error: None,
         }
     }
 
     pub fn quit(&mut self) {
+        // an open-ended session ends here, as does a fixed one whose last guess is in, but
+        // quitting a fixed one part way abandons it
+        if let AppMode::Guessing(ref mut guess) = self.mode
+            && guess.state != GuessState::Finished
+            && (guess.rounds.is_none() || guess.is_last_round())
+        {
+            if guess.is_last_round() {
+                guess.state = GuessState::Finished;
+                guess.elapsed = Some(guess.started.elapsed());
+            }
+            self.record_session();
+        }
         self.running = false;
     }
 
+    /// Appends the session to the leaderboard file, keeping any error to show the player.
+    fn record_session(&mut self) {
+        if let AppMode::Guessing(ref guess) = self.mode
+            && guess.ranked
+            && let Some(path) = &self.leaderboard_path
+            && let Some(entry) = Entry::from_session(guess)
+            && let Err(e) = leaderboard::append(path, &entry)
+        {
+            self.leaderboard_error = Some(format!("could not save to {}: {e}", path.display()));
+        }
+    }
+
+    pub fn toggle_leaderboard(&mut self) {
+        self.show_leaderboard = !self.show_leaderboard;
+        if self.show_leaderboard
+            && let Some(path) = &self.leaderboard_path
+        {
+            match leaderboard::load(path) {
+                Ok(entries) => self.leaderboard = entries,
+                Err(e) => {
+                    self.leaderboard_error = Some(format!("could not read {}: {e}", path.display()))
+                }
+            }
+        }
+    }
+
     pub fn recalc(&mut self) {
-        let (sample, stats) = gen_random_dist(&mut self.rng);
+        // a finished session stays on the scorecard, only 'n' moves on from the last round, and
+        // a fixed session can't skip a hard round by redrawing it before guessing
+        if let AppMode::Guessing(ref guess) = self.mode
+            && (guess.state == GuessState::Finished
+                || guess.is_last_round()
+                || (guess.rounds.is_some() && guess.state == GuessState::WaitingForGuess))
+        {
+            return;
+        }
+        // reseeding per round keeps the curves in step however many draws the reveal used,
+        // and makes a recalculation show the same curve again
+        if let Some(seed) = self.seed {
+            let round = match self.mode {
+                AppMode::Guessing(ref guess) => guess.history.len(),
+                AppMode::Display => 0,
+            };
+            self.rng = round_rng(seed, round as u64);
+        }
+
+        let (sample, stats) = gen_round(&self.config, &mut self.rng);
         self.plot_data = plot_data(&sample);
+        self.underwater = underwater_data(&self.plot_data);
+        self.rolling_sharpe = calc_rolling_sharpe(&sample, &self.config);
+        self.histogram = return_histogram(&sample, HISTOGRAM_BINS);
         self.stats = stats;
+        self.fan_chart = (self.mode == AppMode::Display)
+            .then(|| gen_fan_chart(self.stats.acc_sharpe, &self.config, &mut self.rng));
 
         if let AppMode::Guessing(ref mut guess) = self.mode {
             guess.state = GuessState::WaitingForGuess;
             guess.current_guess.clear();
             guess.last_guess = None;
+            guess.last_points = 0.0;
             guess.guess_was_correct = false;
             // Note: we don't reset score here as it should persist across rounds
         };
     }
 
+    pub fn toggle_histogram(&mut self) {
+        self.show_histogram = !self.show_histogram;
+    }
+
+    #[allow(clippy::collapsible_if)]
     pub fn add_char_to_guess(&mut self, c: char) {
         if let AppMode::Guessing(ref mut guess) = self.mode {
             if guess.state == GuessState::WaitingForGuess {
@@ -93,6 +461,7 @@ impl App {
         }
     }
 
+    #[allow(clippy::collapsible_if)]
     pub fn remove_char_from_guess(&mut self) {
         if let AppMode::Guessing(ref mut guess) = self.mode {
             if guess.state == GuessState::WaitingForGuess {
@@ -103,45 +472,63 @@ impl App {
 
     pub fn toggle_guess_target(&mut self) {
         if let AppMode::Guessing(ref mut guess) = self.mode {
-            guess.target = match guess.target {
-                GuessTarget::Sample => GuessTarget::Actual,
-                GuessTarget::Actual => GuessTarget::Sample,
-            };
+            guess.target = guess.target.next();
         }
     }
 
+    #[allow(clippy::collapsible_if)]
     pub fn submit_guess(&mut self) {
         if let AppMode::Guessing(ref mut guess) = self.mode {
             if guess.state == GuessState::WaitingForGuess {
                 if let Ok(parsed_guess) = guess.current_guess.parse::<f64>() {
                     guess.last_guess = Some(parsed_guess);
 
-                    let sharpe_error = self.stats.sharpe_error;
+                    let target_error = guess.target.error(&self.stats, guess.error_model);
 
                     // Choose the target value based on guess_target
-                    let target_value = match guess.target {
-                        GuessTarget::Sample => self.stats.sample_sharpe,
-                        GuessTarget::Actual => self.stats.acc_sharpe,
-                    };
+                    let target_value = guess.target.value(&self.stats);
 
                     // Check if guess is within error bounds of target
-                    // sample sharpe error ~ 1 std dev - use 0.12 std dev to get about 10% of the dist
-                    if (parsed_guess - target_value).abs() <= 0.12 * sharpe_error {
+                    if (parsed_guess - target_value).abs() <= guess.tolerance * target_error {
                         guess.score += 1;
                         guess.guess_was_correct = true;
                     } else {
                         guess.guess_was_correct = false;
                     }
 
+                    let z = (parsed_guess - target_value).abs() / target_error;
+                    guess.last_points = guess.scoring.points(z);
+                    guess.points += guess.last_points;
+                    guess.history.push(RoundResult {
+                        target: guess.target,
+                        guess: parsed_guess,
+                        value: target_value,
+                        error: target_error,
+                        correct: guess.guess_was_correct,
+                        points: guess.last_points,
+                    });
+
                     guess.state = GuessState::ShowingResult;
+                    self.fan_chart = Some(gen_fan_chart(
+                        self.stats.acc_sharpe,
+                        &self.config,
+                        &mut self.rng,
+                    ));
                 }
             }
         }
     }
 
+    #[allow(clippy::collapsible_if)]
     pub fn next_round(&mut self) {
-        if let AppMode::Guessing(ref guess) = self.mode {
+        if let AppMode::Guessing(ref mut guess) = self.mode {
             if guess.state == GuessState::ShowingResult {
+                if guess.is_last_round() {
+                    guess.state = GuessState::Finished;
+                    guess.elapsed = Some(guess.started.elapsed());
+                    self.record_session();
+                    return;
+                }
                 self.recalc();
             }
         }
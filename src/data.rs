@@ -0,0 +1,123 @@
+use crate::dist::MIN_PERIODS;
+use compact_str::CompactString;
+use std::fs;
+use std::path::Path;
+
+/// A dated historical return series, e.g. the P&L of a real strategy.
+#[derive(Debug, PartialEq)]
+pub struct History {
+    pub dates: Vec<CompactString>,
+    pub returns: Vec<f64>,
+}
+
+impl History {
+    /// Reads `date,value` rows from a CSV file, skipping the first row if it is a header.
+    /// With `prices` the values are price levels and are converted to simple returns,
+    /// so the first date is dropped.
+    pub fn from_csv(path: &Path, prices: bool) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut dates = Vec::new();
+        let mut values = Vec::new();
+
+        let contents = fs::read_to_string(path)?;
+        let rows = contents
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty());
+        for (row, (i, line)) in rows.enumerate() {
+            let mut columns = line.split(',').map(str::trim);
+            let (Some(date), Some(value)) = (columns.next(), columns.next()) else {
+                return Err(format!("line {}: expected date,value", i + 1).into());
+            };
+            match value.parse::<f64>() {
+                Ok(value) => {
+                    dates.push(CompactString::from(date));
+                    values.push(value);
+                }
+                // only the first row may be a header
+                Err(_) if row == 0 => continue,
+                Err(e) => return Err(format!("line {}: {e}", i + 1).into()),
+            }
+        }
+
+        if values.is_empty() {
+            return Err("no data rows found".into());
+        }
+        let returns = if prices {
+            if values.iter().any(|&p| p <= 0.0) {
+                return Err("price levels must be positive".into());
+            }
+            dates.remove(0);
+            values.windows(2).map(|w| w[1] / w[0] - 1.0).collect()
+        } else {
+            values
+        };
+
+        if returns.len() < MIN_PERIODS {
+            return Err(format!(
+                "need at least {MIN_PERIODS} returns, found {}",
+                returns.len()
+            )
+            .into());
+        }
+        Ok(Self { dates, returns })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_csv(name: &str, rows: &[String]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{name}.csv", std::process::id()));
+        fs::write(&path, rows.join("\n")).unwrap();
+        path
+    }
+
+    fn date(day: usize) -> String {
+        format!("2024-01-{:02}", day + 1)
+    }
+
+    #[test]
+    fn from_csv_skips_header_row() {
+        let mut rows = vec!["date,return".to_string()];
+        rows.extend((0..MIN_PERIODS).map(|i| format!("{},{}", date(i), i as f64 / 100.0)));
+        let path = write_csv("returns", &rows);
+        let history = History::from_csv(&path, false).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(history.returns.len(), MIN_PERIODS);
+        assert_eq!(history.dates[0], "2024-01-01");
+        assert_eq!(history.returns[1], 0.01);
+    }
+
+    #[test]
+    fn from_csv_rejects_bad_rows_after_the_header() {
+        let mut rows = vec![
+            "date,return".to_string(),
+            String::new(),
+            "2023-12-31,n/a".to_string(),
+        ];
+        rows.extend((0..MIN_PERIODS).map(|i| format!("{},{}", date(i), i as f64 / 100.0)));
+        let path = write_csv("bad-rows", &rows);
+        let error = History::from_csv(&path, false).unwrap_err();
+        fs::remove_file(path).unwrap();
+
+        assert!(error.to_string().starts_with("line 3: "), "{error}");
+    }
+
+    #[test]
+    fn from_csv_converts_prices_to_returns() {
+        let rows: Vec<String> = (0..=MIN_PERIODS)
+            .map(|i| format!("{},{}", date(i), 100.0 * 1.1_f64.powi(i as i32 % 2)))
+            .collect();
+        let path = write_csv("prices", &rows);
+        let history = History::from_csv(&path, true).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(history.returns.len(), MIN_PERIODS);
+        assert_eq!(history.dates[0], "2024-01-02");
+        assert!((history.returns[0] - 0.1).abs() < 1e-12);
+        assert!((history.returns[1] + 1.0 / 11.0).abs() < 1e-12);
+    }
+}
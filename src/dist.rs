@@ -1,47 +1,644 @@
+use crate::data::History;
 use rand::distr::StandardUniform;
 use rand::prelude::*;
 use rand_chacha::ChaCha20Rng;
-use rand_distr::num_traits::Float;
-use rand_distr::{Distribution, Normal};
+use rand_distr::{Distribution, Normal, Poisson, StandardNormal, StudentT};
+use std::rc::Rc;
 
-pub const DAYS: usize = 504; // Number of trading days in 2 years - 252 days per year
+pub const DEFAULT_YEARS: f64 = 2.0; // Default track record length, e.g. 504 trading days
+pub const MIN_PERIODS: usize = 20; // Shortest track record with enough data for the autocorrelation lags
+const LO_LAGS: usize = 10; // Autocorrelation lags used in the Lo (2002) standard error
+const SPREAD_SIMS: usize = 200; // Paths simulated to estimate the sampling spread of path statistics
+pub const FAN_SIMS: usize = 300; // Alternative paths behind the fan chart shown on reveal
+
+/// Process used to draw the daily returns of a track record.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReturnModel {
+    /// IID Gaussian daily returns.
+    Normal,
+    /// IID Student-t innovations with `dof` degrees of freedom, rescaled to unit variance.
+    StudentT { dof: f64 },
+    /// GARCH(1,1) volatility clustering, normalized so the long-run variance matches `Normal`.
+    Garch { omega: f64, alpha: f64, beta: f64 },
+    /// Two-state Markov chain over the true Sharpe, switching `switch_rate` times per year on average.
+    RegimeSwitching { switch_rate: f64 },
+    /// AR(1) returns with lag-one autocorrelation `phi`, keeping the unconditional variance of `Normal`.
+    Ar1 { phi: f64 },
+    /// Merton jump-diffusion: Poisson jumps (`intensity` per year) of Normal size, measured in
+    /// daily standard deviations, added to the diffusion and rescaled to keep the Sharpe.
+    JumpDiffusion {
+        intensity: f64,
+        jump_mean: f64,
+        jump_std: f64,
+    },
+    /// Random windows of a historical series, whose full-sample Sharpe stands in for the true one.
+    Historical(Rc<History>),
+    /// Stationary block bootstrap of a historical series with blocks of mean length `mean_block`.
+    /// With `redrift` the history is demeaned and rescaled so the true Sharpe comes from the prior,
+    /// otherwise the full-sample Sharpe of the history is the true one.
+    Bootstrap {
+        history: Rc<History>,
+        mean_block: f64,
+        redrift: bool,
+    },
+}
+
+/// Distribution the true Sharpe ratio of each round is drawn from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Prior {
+    Uniform {
+        min: f64,
+        max: f64,
+    },
+    Normal {
+        mean: f64,
+        std: f64,
+    },
+    Point(f64),
+    /// Equally likely Sharpe ratios, e.g. from a universe of real strategies.
+    Empirical(Vec<f64>),
+}
+
+/// Sampling frequency of the returns in a track record.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    pub fn periods_per_year(self) -> f64 {
+        match self {
+            Frequency::Daily => 252.0,
+            Frequency::Weekly => 52.0,
+            Frequency::Monthly => 12.0,
+        }
+    }
+
+    /// Name of a single period, used to label the time axis.
+    pub fn period_name(self) -> &'static str {
+        match self {
+            Frequency::Daily => "Day",
+            Frequency::Weekly => "Week",
+            Frequency::Monthly => "Month",
+        }
+    }
+}
+
+/// Everything needed to simulate a round: the prior on the true Sharpe, the return process
+/// and the number and frequency of observations in the track record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimConfig {
+    pub prior: Prior,
+    pub model: ReturnModel,
+    pub periods: usize,
+    pub frequency: Frequency,
+    /// Number of backtests simulated per round, of which only the best is shown.
+    pub trials: usize,
+    /// Benchmark Sharpe for the probabilistic Sharpe ratio.
+    pub benchmark_sharpe: f64,
+}
+
+/// A stretch of periods sharing the same true Sharpe ratio, starting at period `start`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Regime {
+    pub start: usize,
+    pub sharpe: f64,
+}
+
+/// The deepest peak-to-trough fall of the cumulative returns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drawdown {
+    /// Fall from the running peak, in cumulative return.
+    pub max_drawdown: f64,
+    /// Period of the high the fall starts from, or `None` for the starting level before the
+    /// first return.
+    pub peak: Option<usize>,
+    pub trough: usize,
+    /// Periods from the peak until the previous high is regained, or until the end if it never is.
+    pub duration: usize,
+    /// Periods from the trough until the previous high is regained.
+    pub recovery: Option<usize>,
+}
+
+/// Risk-adjusted performance measures of a path, annualized where applicable.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RiskMetrics {
+    pub volatility: f64,
+    /// Annual return over annualized downside deviation below zero.
+    pub sortino: f64,
+    /// Annual return over max drawdown.
+    pub calmar: f64,
+    /// Sum of gains over sum of losses.
+    pub omega: f64,
+    /// 95th percentile return over the magnitude of the 5th percentile return.
+    pub tail_ratio: f64,
+    pub max_drawdown: f64,
+}
+
+/// How the shown path was picked out of several simulated backtests.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Selection {
+    pub trials: usize,
+    /// Expected maximum sample Sharpe of `trials` strategies with zero true Sharpe.
+    pub expected_max_sharpe: f64,
+}
+
+/// Standard error formula used for the sample Sharpe ratio.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ErrorModel {
+    /// IID Gaussian returns
+    Iid,
+    /// Lo (2002) adjustment for autocorrelation
+    Lo,
+    /// Mertens (2002) / Opdyke (2007) adjustment for skew and kurtosis
+    Mertens,
+}
 
 #[derive(Debug)]
 pub struct Stats {
     pub acc_sharpe: f64,
+    pub posterior_sharpe: f64,
     pub sample_sharpe: f64,
     pub sharpe_error: f64,
+    pub lo_sharpe_error: f64,
+    pub mertens_sharpe_error: f64,
+    pub autocorr: f64,
+    pub skew: f64,
+    pub excess_kurtosis: f64,
     pub sample_mean: f64,
     pub sample_max: f64,
     pub sample_min: f64,
+    pub drawdown: Drawdown,
+    pub metrics: RiskMetrics,
+    /// Standard deviation of each metric across paths with the same true Sharpe.
+    pub metric_errors: RiskMetrics,
+    pub regimes: Vec<Regime>,
+    /// Probability that the true Sharpe exceeds the configured benchmark.
+    pub probabilistic_sharpe: f64,
+    /// Probability that the true Sharpe exceeds the best Sharpe expected by chance across
+    /// all trials, i.e. that the path is not just the luckiest of the backtests.
+    pub deflated_sharpe: f64,
+    /// Set when the path shown is the best of several backtests.
+    pub selection: Option<Selection>,
+    /// Index of the first return in the historical series, for rounds drawn from real data.
+    pub window_start: Option<usize>,
+}
+
+impl SimConfig {
+    /// What `Stats::acc_sharpe` means for this config: the true Sharpe, or for historical
+    /// data the full-sample Sharpe.
+    pub fn reference_name(&self) -> &'static str {
+        match self.model {
+            ReturnModel::Historical(_) | ReturnModel::Bootstrap { redrift: false, .. } => {
+                "Full-sample"
+            }
+            _ => "Actual",
+        }
+    }
+}
+
+impl Stats {
+    pub fn error(&self, model: ErrorModel) -> f64 {
+        match model {
+            ErrorModel::Iid => self.sharpe_error,
+            ErrorModel::Lo => self.lo_sharpe_error,
+            ErrorModel::Mertens => self.mertens_sharpe_error,
+        }
+    }
+}
+
+impl Prior {
+    /// Draws a true Sharpe ratio from the prior.
+    fn sample(&self, rng: &mut ChaCha20Rng) -> f64 {
+        match self {
+            Prior::Uniform { min, max } => {
+                min + rng.sample::<f64, _>(StandardUniform) * (max - min)
+            }
+            Prior::Normal { mean, std } => Normal::new(*mean, *std).unwrap().sample(rng),
+            Prior::Point(sharpe) => *sharpe,
+            Prior::Empirical(sharpes) => *sharpes.choose(rng).unwrap(),
+        }
+    }
+
+    /// Posterior mean of the true Sharpe after observing `sample_sharpe`, treating the sample
+    /// as Normal around the true value with standard deviation `sharpe_error`.
+    pub fn posterior_mean(&self, sample_sharpe: f64, sharpe_error: f64) -> f64 {
+        let precision = sharpe_error.powi(2).recip();
+        match self {
+            Prior::Uniform { min, max } => {
+                // mean of a Normal(sample, error) truncated to [min, max]
+                let alpha = (min - sample_sharpe) / sharpe_error;
+                let beta = (max - sample_sharpe) / sharpe_error;
+                let mass = norm_cdf(beta) - norm_cdf(alpha);
+                if mass > f64::EPSILON {
+                    sample_sharpe + sharpe_error * (norm_pdf(alpha) - norm_pdf(beta)) / mass
+                } else {
+                    sample_sharpe.clamp(*min, *max)
+                }
+            }
+            Prior::Normal { mean, std } => {
+                let prior_precision = std.powi(2).recip();
+                (mean * prior_precision + sample_sharpe * precision) / (prior_precision + precision)
+            }
+            Prior::Point(sharpe) => *sharpe,
+            Prior::Empirical(sharpes) => {
+                // weight each candidate by its likelihood, shifted by the best to avoid underflow
+                let log_lik = |x: f64| -0.5 * (x - sample_sharpe).powi(2) * precision;
+                let best = sharpes
+                    .iter()
+                    .map(|&x| log_lik(x))
+                    .fold(f64::NEG_INFINITY, f64::max);
+                let (weighted, total) = sharpes.iter().fold((0.0, 0.0), |(weighted, total), &x| {
+                    let w = (log_lik(x) - best).exp();
+                    (weighted + w * x, total + w)
+                });
+                weighted / total
+            }
+        }
+    }
+}
+
+/// Standard normal density.
+pub fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
 }
 
-/// Generates a random Sharpe ratio in the range of -3 to 3.
-fn gen_rand_sharpe(rng: &mut ChaCha20Rng) -> f64 {
-    let sharpe: f64 = (rng.sample::<f64, _>(StandardUniform) * 6.0) - 3.0; // Generate a number between -3 and 3
-    sharpe
+/// Standard normal CDF via the Abramowitz and Stegun 7.1.26 approximation of erf (error < 1.5e-7).
+fn norm_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = (1.0 + 0.327_591_1 * z).recip();
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    0.5 * (1.0 + erf.copysign(x))
 }
 
-fn gen_return_series(sharpe: f64, rng: &mut ChaCha20Rng) -> [f64; DAYS] {
-    let mut returns = [0.; DAYS];
+/// Inverse of the standard normal CDF using Acklam's rational approximation (relative error < 1.2e-9).
+fn norm_ppf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Expected maximum of `trials` sample Sharpe ratios with zero true Sharpe and cross-trial
+/// standard deviation `sharpe_std` (Bailey and Lopez de Prado, 2014).
+fn expected_max_sharpe(trials: usize, sharpe_std: f64) -> f64 {
+    if trials < 2 {
+        return 0.0;
+    }
+    let n = trials as f64;
+    let gamma = 0.577_215_664_901_532_9; // Euler-Mascheroni constant
+    sharpe_std
+        * ((1.0 - gamma) * norm_ppf(1.0 - n.recip())
+            + gamma * norm_ppf(1.0 - (n * std::f64::consts::E).recip()))
+}
+
+/// Probabilistic Sharpe ratio: probability that the true Sharpe exceeds `benchmark`, allowing
+/// for skew and kurtosis of the returns (Bailey and Lopez de Prado, 2012).
+fn probabilistic_sharpe(
+    stats: &Stats,
+    benchmark: f64,
+    periods: usize,
+    periods_per_year: f64,
+) -> f64 {
+    let sharpe = stats.sample_sharpe / periods_per_year.sqrt();
+    let benchmark = benchmark / periods_per_year.sqrt();
+    let var = 1.0 - stats.skew * sharpe + (stats.excess_kurtosis + 2.0) / 4.0 * sharpe.powi(2);
+    norm_cdf((sharpe - benchmark) * ((periods - 1) as f64).sqrt() / var.max(f64::EPSILON).sqrt())
+}
+
+fn gen_return_series(sharpe: f64, config: &SimConfig, rng: &mut ChaCha20Rng) -> Vec<f64> {
+    let mut returns = vec![0.; config.periods];
     // annual sharpe = mu / sigma - assume sigma = 1.0 so annual mu = sharpe
-    // in daily terms this means mu = sharpe / 252 and sigma = 1.0 / sqrt(252)
-    let normal = Normal::new(sharpe / 252., 252.0.sqrt().recip()).unwrap();
+    // per period this means mu = sharpe / periods_per_year and sigma = 1.0 / sqrt(periods_per_year)
+    let periods_per_year = config.frequency.periods_per_year();
+    let mu = sharpe / periods_per_year;
+    let sigma = periods_per_year.sqrt().recip();
 
-    returns.iter_mut().for_each(|x| *x = normal.sample(rng));
+    match &config.model {
+        ReturnModel::Normal => {
+            let normal = Normal::new(mu, sigma).unwrap();
+            returns.iter_mut().for_each(|x| *x = normal.sample(rng));
+        }
+        ReturnModel::StudentT { dof } => {
+            // a t variable with dof degrees of freedom has variance dof / (dof - 2),
+            // so rescale to unit variance to keep the annual sharpe at mu / sigma
+            let student_t = StudentT::new(*dof).unwrap();
+            let scale = sigma * ((dof - 2.0) / dof).sqrt();
+            returns
+                .iter_mut()
+                .for_each(|x| *x = mu + scale * student_t.sample(rng));
+        }
+        ReturnModel::Garch { omega, alpha, beta } => {
+            // h_t = omega + alpha * e_{t-1}^2 + beta * h_{t-1}, started at the long-run
+            // variance omega / (1 - alpha - beta) which is then rescaled to sigma^2
+            let long_run_var = omega / (1.0 - alpha - beta);
+            let scale = sigma / long_run_var.sqrt();
+            let mut var = long_run_var;
+            for x in returns.iter_mut() {
+                let z: f64 = StandardNormal.sample(rng);
+                let shock = var.sqrt() * z;
+                *x = mu + scale * shock;
+                var = omega + alpha * shock.powi(2) + beta * var;
+            }
+        }
+        ReturnModel::RegimeSwitching { switch_rate } => {
+            return gen_regime_series(&[sharpe, sharpe], *switch_rate, config, rng).0;
+        }
+        ReturnModel::Historical(history) => {
+            return gen_window(history, config.periods, rng).1;
+        }
+        ReturnModel::Bootstrap {
+            history,
+            mean_block,
+            redrift,
+        } => {
+            let resampled =
+                gen_stationary_bootstrap(&history.returns, *mean_block, config.periods, rng);
+            if !redrift {
+                return resampled;
+            }
+            // standardize with the moments of the full history, then impose the target drift
+            let (hist_mu, hist_std) = mean_std(&history.returns);
+            returns
+                .iter_mut()
+                .zip(resampled)
+                .for_each(|(x, r)| *x = mu + sigma * (r - hist_mu) / hist_std);
+        }
+        ReturnModel::Ar1 { phi } => {
+            // e_t = phi * e_{t-1} + sqrt(1 - phi^2) * sigma * z_t is stationary with variance
+            // sigma^2, so start from that distribution and the sharpe is unchanged
+            let innovation = Normal::new(0.0, sigma * (1.0 - phi.powi(2)).sqrt()).unwrap();
+            let mut shock = Normal::new(0.0, sigma).unwrap().sample(rng);
+            for x in returns.iter_mut() {
+                *x = mu + shock;
+                shock = phi * shock + innovation.sample(rng);
+            }
+        }
+        ReturnModel::JumpDiffusion {
+            intensity,
+            jump_mean,
+            jump_std,
+        } => {
+            // the compound Poisson term has mean rate * jump_mean and variance
+            // rate * (jump_mean^2 + jump_std^2) - remove the former from the drift and
+            // shrink the whole shock so its variance is back to sigma^2
+            let period_rate = intensity / periods_per_year;
+            let jump_var = period_rate * (jump_mean.powi(2) + jump_std.powi(2));
+            let scale = sigma / (1.0 + jump_var).sqrt();
+            let arrivals = Poisson::new(period_rate).unwrap();
+            let jump_size = Normal::new(*jump_mean, *jump_std).unwrap();
+            for x in returns.iter_mut() {
+                let z: f64 = StandardNormal.sample(rng);
+                let jump: f64 = (0..arrivals.sample(rng) as usize)
+                    .map(|_| jump_size.sample(rng))
+                    .sum();
+                *x = mu + scale * (z + jump - period_rate * jump_mean);
+            }
+        }
+    }
     returns
 }
 
-fn calc_sample_sharpe(sample: &[f64; DAYS]) -> (f64, f64) {
-    let sample_mu = sample.iter().sum::<f64>() / DAYS as f64;
-    let sample_var = sample.iter().map(|x| (x - sample_mu).powi(2)).sum::<f64>() / DAYS as f64;
-    let sample_std = sample_var.sqrt();
+/// Picks a random window of `periods` consecutive returns from the history, returning the
+/// index of its first return alongside it.
+fn gen_window(history: &History, periods: usize, rng: &mut ChaCha20Rng) -> (usize, Vec<f64>) {
+    let start = rng.random_range(0..=history.returns.len() - periods);
+    (start, history.returns[start..start + periods].to_vec())
+}
+
+/// Politis and Romano (1994) stationary bootstrap: blocks start at random points in the sample
+/// and have geometric lengths with mean `mean_block`, wrapping around the end of the sample.
+fn gen_stationary_bootstrap(
+    sample: &[f64],
+    mean_block: f64,
+    periods: usize,
+    rng: &mut ChaCha20Rng,
+) -> Vec<f64> {
+    let mut idx = rng.random_range(0..sample.len());
+    (0..periods)
+        .map(|_| {
+            let x = sample[idx];
+            idx = if rng.random_bool(mean_block.recip()) {
+                rng.random_range(0..sample.len())
+            } else {
+                (idx + 1) % sample.len()
+            };
+            x
+        })
+        .collect()
+}
+
+/// Generates returns whose true Sharpe flips between the two `states` following a Markov chain,
+/// recording each regime change.
+fn gen_regime_series(
+    states: &[f64; 2],
+    switch_rate: f64,
+    config: &SimConfig,
+    rng: &mut ChaCha20Rng,
+) -> (Vec<f64>, Vec<Regime>) {
+    let mut returns = vec![0.; config.periods];
+    let periods_per_year = config.frequency.periods_per_year();
+    // probability of at least one switch in a period when switches arrive as a Poisson process
+    let switch_prob = 1.0 - (-switch_rate / periods_per_year).exp();
+    let noise = Normal::new(0.0, periods_per_year.sqrt().recip()).unwrap();
+    let mut state = 0;
+    let mut regimes = vec![Regime {
+        start: 0,
+        sharpe: states[0],
+    }];
+
+    for (day, x) in returns.iter_mut().enumerate() {
+        if day > 0 && rng.random_bool(switch_prob) {
+            state = 1 - state;
+            regimes.push(Regime {
+                start: day,
+                sharpe: states[state],
+            });
+        }
+        *x = states[state] / periods_per_year + noise.sample(rng);
+    }
+    (returns, regimes)
+}
+
+/// Period-weighted average of the regime Sharpe ratios: with constant volatility this is the
+/// true Sharpe of the path as a whole.
+fn regime_average_sharpe(regimes: &[Regime], periods: usize) -> f64 {
+    regimes
+        .iter()
+        .enumerate()
+        .map(|(i, regime)| {
+            let end = regimes.get(i + 1).map_or(periods, |next| next.start);
+            regime.sharpe * (end - regime.start) as f64
+        })
+        .sum::<f64>()
+        / periods as f64
+}
+
+fn mean_std(sample: &[f64]) -> (f64, f64) {
+    let n = sample.len() as f64;
+    let mu = sample.iter().sum::<f64>() / n;
+    let var = sample.iter().map(|x| (x - mu).powi(2)).sum::<f64>() / n;
+    (mu, var.sqrt())
+}
+
+fn calc_sample_sharpe(sample: &[f64], periods_per_year: f64) -> (f64, f64) {
+    let (sample_mu, sample_std) = mean_std(sample);
     // Annualize the Sharpe ratio: multiply mean by 252 and std by sqrt(252) = overall by sqrt 252
+    // (or 52 / 12 for weekly / monthly returns)
+
+    (
+        (sample_mu / sample_std) * periods_per_year.sqrt(),
+        sample_mu,
+    )
+}
 
-    ((sample_mu / sample_std) * 252.0_f64.sqrt(), sample_mu)
+/// Sample autocorrelations of the series for lags 1 to `LO_LAGS`.
+fn sample_autocorr(sample: &[f64], sample_mu: f64) -> [f64; LO_LAGS] {
+    let var = sample.iter().map(|x| (x - sample_mu).powi(2)).sum::<f64>();
+    std::array::from_fn(|i| {
+        sample
+            .windows(i + 2)
+            .map(|w| (w[0] - sample_mu) * (w[i + 1] - sample_mu))
+            .sum::<f64>()
+            / var
+    })
+}
+
+/// Lo (2002) adjustment of the iid sharpe error for serially correlated returns: the variance
+/// of the mean is scaled by the Newey-West long-run variance ratio 1 + 2 sum_k w_k rho_k.
+fn lo_sharpe_error(sharpe_error: f64, autocorr: &[f64; LO_LAGS]) -> f64 {
+    let long_run_ratio = 1.0
+        + 2.0
+            * autocorr
+                .iter()
+                .enumerate()
+                .map(|(i, rho)| (1.0 - (i + 1) as f64 / (LO_LAGS + 1) as f64) * rho)
+                .sum::<f64>();
+    // strongly negative sample autocorrelation can push the estimated ratio below zero
+    sharpe_error * long_run_ratio.max(0.0).sqrt()
 }
 
-fn sample_min_max(sample: &[f64; DAYS]) -> (f64, f64) {
+/// Sample skewness and excess kurtosis of the series.
+fn sample_moments(sample: &[f64], sample_mu: f64) -> (f64, f64) {
+    let n = sample.len() as f64;
+    let central = |p: i32| sample.iter().map(|x| (x - sample_mu).powi(p)).sum::<f64>() / n;
+    let var = central(2);
+    (central(3) / var.powf(1.5), central(4) / var.powi(2) - 3.0)
+}
+
+/// Mertens (2002) standard error of the daily Sharpe, valid for non-normal iid returns:
+/// Var(SR) = (1 - skew * SR + (excess_kurtosis + 2) / 4 * SR^2) / T, then annualized.
+fn mertens_sharpe_error(
+    sample_sharpe: f64,
+    skew: f64,
+    excess_kurtosis: f64,
+    periods: usize,
+    periods_per_year: f64,
+) -> f64 {
+    let period_sharpe = sample_sharpe / periods_per_year.sqrt();
+    let var = 1.0 - skew * period_sharpe + (excess_kurtosis + 2.0) / 4.0 * period_sharpe.powi(2);
+    (var / periods as f64).sqrt() * periods_per_year.sqrt()
+}
+
+/// Finds the maximum drawdown of a cumulative return series, measured from a starting level of zero.
+fn calc_drawdown(cumulative: &[f64]) -> Drawdown {
+    let (mut peak, mut peak_level) = (None, 0.0);
+    let mut worst = (0.0, None, 0, 0.0);
+    for (i, &level) in cumulative.iter().enumerate() {
+        if level > peak_level {
+            (peak, peak_level) = (Some(i), level);
+        } else if peak_level - level > worst.0 {
+            worst = (peak_level - level, peak, i, peak_level);
+        }
+    }
+
+    let (max_drawdown, peak, trough, high) = worst;
+    let recovery = cumulative[trough..].iter().position(|&level| level >= high);
+    // the starting level sits one period before the first return
+    let start = peak.map_or(0, |peak| peak + 1);
+    Drawdown {
+        max_drawdown,
+        peak,
+        trough,
+        duration: recovery.map_or(cumulative.len() + 1, |r| trough + r + 1) - start,
+        recovery,
+    }
+}
+
+/// Linearly interpolated `q` quantile of an ascending sorted sample.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+fn calc_risk_metrics(sample: &[f64], max_drawdown: f64, periods_per_year: f64) -> RiskMetrics {
+    let (mu, std) = mean_std(sample);
+    let downside_dev =
+        (sample.iter().map(|x| x.min(0.0).powi(2)).sum::<f64>() / sample.len() as f64).sqrt();
+    let gains = sample.iter().map(|x| x.max(0.0)).sum::<f64>();
+    let losses = sample.iter().map(|x| (-x).max(0.0)).sum::<f64>();
+    let mut sorted = sample.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    RiskMetrics {
+        volatility: std * periods_per_year.sqrt(),
+        sortino: mu / downside_dev * periods_per_year.sqrt(),
+        calmar: mu * periods_per_year / max_drawdown,
+        omega: gains / losses,
+        tail_ratio: quantile(&sorted, 0.95) / quantile(&sorted, 0.05).abs(),
+        max_drawdown,
+    }
+}
+
+fn sample_min_max(sample: &[f64]) -> (f64, f64) {
     let min = f64::INFINITY;
     let max = f64::NEG_INFINITY;
 
@@ -50,30 +647,384 @@ fn sample_min_max(sample: &[f64; DAYS]) -> (f64, f64) {
         .fold((min, max), |(min, max), &x| (min.min(x), max.max(x)))
 }
 
-pub fn gen_random_dist(rng: &mut ChaCha20Rng) -> ([f64; DAYS], Stats) {
-    let acc_sharpe = gen_rand_sharpe(rng);
-    let returns = gen_return_series(acc_sharpe, rng);
-    let (sample_sharpe, sample_mu) = calc_sample_sharpe(&returns);
+pub fn gen_random_dist(config: &SimConfig, rng: &mut ChaCha20Rng) -> (Vec<f64>, Stats) {
+    let periods = config.periods;
+    let periods_per_year = config.frequency.periods_per_year();
+    let mut window_start = None;
+    let (returns, regimes) = match config.model {
+        ReturnModel::RegimeSwitching { switch_rate } => {
+            let states = [config.prior.sample(rng), config.prior.sample(rng)];
+            gen_regime_series(&states, switch_rate, config, rng)
+        }
+        ReturnModel::Historical(ref history) => {
+            let (start, returns) = gen_window(history, periods, rng);
+            window_start = Some(start);
+            let (sharpe, _) = calc_sample_sharpe(&history.returns, periods_per_year);
+            (returns, vec![Regime { start: 0, sharpe }])
+        }
+        ReturnModel::Bootstrap {
+            ref history,
+            redrift: false,
+            ..
+        } => {
+            let (sharpe, _) = calc_sample_sharpe(&history.returns, periods_per_year);
+            let returns = gen_return_series(sharpe, config, rng);
+            (returns, vec![Regime { start: 0, sharpe }])
+        }
+        _ => {
+            let sharpe = config.prior.sample(rng);
+            let returns = gen_return_series(sharpe, config, rng);
+            (returns, vec![Regime { start: 0, sharpe }])
+        }
+    };
+    let acc_sharpe = regime_average_sharpe(&regimes, periods);
+    let (sample_sharpe, sample_mu) = calc_sample_sharpe(&returns, periods_per_year);
     let (sample_min, sample_max) = sample_min_max(&returns);
+    let cumulative: Vec<f64> = plot_data(&returns).into_iter().map(|(_, y)| y).collect();
+    let drawdown = calc_drawdown(&cumulative);
+    let metrics = calc_risk_metrics(&returns, drawdown.max_drawdown, periods_per_year);
     // Calculate sample sharpe error: sqrt((1 + sharpe^2 / 2) / T)
     let sharpe_error =
-        ((1.0 + sample_sharpe.powi(2) / 2.0) / DAYS as f64).sqrt() * (252.0_f64.sqrt());
+        ((1.0 + sample_sharpe.powi(2) / 2.0) / periods as f64).sqrt() * periods_per_year.sqrt();
+    let autocorrs = sample_autocorr(&returns, sample_mu);
+    let lo_sharpe_error = lo_sharpe_error(sharpe_error, &autocorrs);
+    let (skew, excess_kurtosis) = sample_moments(&returns, sample_mu);
+    let mertens_sharpe_error = mertens_sharpe_error(
+        sample_sharpe,
+        skew,
+        excess_kurtosis,
+        periods,
+        periods_per_year,
+    );
+    let posterior_sharpe = config
+        .prior
+        .posterior_mean(sample_sharpe, mertens_sharpe_error);
 
-    let stats = Stats {
+    let mut stats = Stats {
         acc_sharpe,
+        posterior_sharpe,
         sample_sharpe,
         sharpe_error,
+        lo_sharpe_error,
+        mertens_sharpe_error,
+        autocorr: autocorrs[0],
+        skew,
+        excess_kurtosis,
         sample_mean: sample_mu,
         sample_max,
         sample_min,
+        drawdown,
+        metrics,
+        metric_errors: RiskMetrics::default(),
+        regimes,
+        probabilistic_sharpe: 0.0,
+        deflated_sharpe: 0.0,
+        selection: None,
+        window_start,
     };
+    stats.probabilistic_sharpe =
+        probabilistic_sharpe(&stats, config.benchmark_sharpe, periods, periods_per_year);
+    // a single trial has nothing to deflate for, so the hurdle is a zero Sharpe
+    stats.deflated_sharpe = probabilistic_sharpe(&stats, 0.0, periods, periods_per_year);
     (returns, stats)
 }
 
-pub fn plot_data<const N: usize>(day_data: &[f64; N]) -> [(f64, f64); N] {
-    let mut cumulative_return = 0.0;
-    std::array::from_fn(|i| {
-        cumulative_return += day_data[i];
-        (i as f64, cumulative_return)
-    })
+/// Generates the path shown in a round: a single draw, or the best of `config.trials`
+/// backtests by sample Sharpe along with the selection statistics. The spread of path
+/// statistics is then estimated by simulating more paths with the same true Sharpe.
+pub fn gen_round(config: &SimConfig, rng: &mut ChaCha20Rng) -> (Vec<f64>, Stats) {
+    let (returns, mut stats) = if config.trials < 2 {
+        gen_random_dist(config, rng)
+    } else {
+        gen_best_of(config, rng)
+    };
+
+    let periods_per_year = config.frequency.periods_per_year();
+    let sims: Vec<RiskMetrics> = (0..SPREAD_SIMS)
+        .map(|_| {
+            let path = gen_return_series(stats.acc_sharpe, config, rng);
+            let cumulative: Vec<f64> = plot_data(&path).into_iter().map(|(_, y)| y).collect();
+            let max_drawdown = calc_drawdown(&cumulative).max_drawdown;
+            calc_risk_metrics(&path, max_drawdown, periods_per_year)
+        })
+        .collect();
+    let spread = |metric: fn(&RiskMetrics) -> f64| {
+        let values: Vec<f64> = sims.iter().map(metric).filter(|x| x.is_finite()).collect();
+        mean_std(&values).1
+    };
+    stats.metric_errors = RiskMetrics {
+        volatility: spread(|m| m.volatility),
+        sortino: spread(|m| m.sortino),
+        calmar: spread(|m| m.calmar),
+        omega: spread(|m| m.omega),
+        tail_ratio: spread(|m| m.tail_ratio),
+        max_drawdown: spread(|m| m.max_drawdown),
+    };
+    (returns, stats)
+}
+
+fn gen_best_of(config: &SimConfig, rng: &mut ChaCha20Rng) -> (Vec<f64>, Stats) {
+    let mut trial_sharpes = Vec::with_capacity(config.trials);
+    let mut best = gen_random_dist(config, rng);
+    trial_sharpes.push(best.1.sample_sharpe);
+    for _ in 1..config.trials {
+        let trial = gen_random_dist(config, rng);
+        trial_sharpes.push(trial.1.sample_sharpe);
+        if trial.1.sample_sharpe > best.1.sample_sharpe {
+            best = trial;
+        }
+    }
+
+    let (_, sharpe_std) = mean_std(&trial_sharpes);
+    let expected_max_sharpe = expected_max_sharpe(config.trials, sharpe_std);
+    let periods_per_year = config.frequency.periods_per_year();
+    let (returns, mut stats) = best;
+    stats.deflated_sharpe = probabilistic_sharpe(
+        &stats,
+        expected_max_sharpe,
+        config.periods,
+        periods_per_year,
+    );
+    stats.selection = Some(Selection {
+        trials: config.trials,
+        expected_max_sharpe,
+    });
+    (returns, stats)
+}
+
+/// Quarter and half-year rolling windows, e.g. 63 and 126 trading days.
+pub fn rolling_windows(frequency: Frequency) -> [usize; 2] {
+    let periods_per_year = frequency.periods_per_year();
+    [
+        (periods_per_year / 4.0).round() as usize,
+        (periods_per_year / 2.0).round() as usize,
+    ]
+}
+
+/// Annualized Sharpe ratio of each trailing `window`, plotted at the window's last period.
+pub fn rolling_sharpe(day_data: &[f64], window: usize, periods_per_year: f64) -> Vec<(f64, f64)> {
+    day_data
+        .windows(window)
+        .enumerate()
+        .map(|(i, w)| {
+            let (sharpe, _) = calc_sample_sharpe(w, periods_per_year);
+            ((i + window - 1) as f64, sharpe)
+        })
+        .collect()
+}
+
+/// Percentile bands of cumulative returns across simulated paths with the same true Sharpe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FanChart {
+    pub lower: Vec<(f64, f64)>,
+    pub median: Vec<(f64, f64)>,
+    pub upper: Vec<(f64, f64)>,
+}
+
+pub fn gen_fan_chart(sharpe: f64, config: &SimConfig, rng: &mut ChaCha20Rng) -> FanChart {
+    let paths: Vec<Vec<(f64, f64)>> = (0..FAN_SIMS)
+        .map(|_| plot_data(&gen_return_series(sharpe, config, rng)))
+        .collect();
+
+    let mut fan = FanChart {
+        lower: Vec::with_capacity(config.periods),
+        median: Vec::with_capacity(config.periods),
+        upper: Vec::with_capacity(config.periods),
+    };
+    let mut levels = Vec::with_capacity(FAN_SIMS);
+    for t in 0..config.periods {
+        levels.clear();
+        levels.extend(paths.iter().map(|path| path[t].1));
+        levels.sort_by(f64::total_cmp);
+        let x = t as f64;
+        fan.lower.push((x, quantile(&levels, 0.05)));
+        fan.median.push((x, quantile(&levels, 0.5)));
+        fan.upper.push((x, quantile(&levels, 0.95)));
+    }
+    fan
+}
+
+/// Binned returns with the fitted normal density scaled to expected counts per bin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub counts: Vec<u64>,
+    pub min: f64,
+    pub max: f64,
+    /// Expected count against position measured in bins, so bin `i` spans `i..i + 1`.
+    pub normal_fit: Vec<(f64, f64)>,
+}
+
+pub fn return_histogram(sample: &[f64], bins: usize) -> Histogram {
+    let (min, max) = sample_min_max(sample);
+    let bin_width = if max > min {
+        (max - min) / bins as f64
+    } else {
+        1.0
+    };
+
+    let mut counts = vec![0; bins];
+    for x in sample {
+        let bin = ((x - min) / bin_width) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+
+    // a few points per bin keeps the curve smooth at braille resolution
+    let (mu, std) = mean_std(sample);
+    let scale = sample.len() as f64 * bin_width / std;
+    let points = bins * 4;
+    let normal_fit = (0..=points)
+        .map(|i| {
+            let pos = i as f64 * bins as f64 / points as f64;
+            let x = min + pos * bin_width;
+            (pos, scale * norm_pdf((x - mu) / std))
+        })
+        .collect();
+
+    Histogram {
+        counts,
+        min,
+        max,
+        normal_fit,
+    }
+}
+
+/// Drawdown of a cumulative return curve from its running peak, starting flat. Returns are
+/// summed rather than compounded, so this is in the same units as `Drawdown::max_drawdown`.
+pub fn underwater_data(plot_data: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    plot_data
+        .iter()
+        .scan(0.0_f64, |peak, &(x, level)| {
+            *peak = peak.max(level);
+            Some((x, level - *peak))
+        })
+        .collect()
+}
+
+pub fn plot_data(day_data: &[f64]) -> Vec<(f64, f64)> {
+    day_data
+        .iter()
+        .scan(0.0, |cumulative_return, x| {
+            *cumulative_return += x;
+            Some(*cumulative_return)
+        })
+        .enumerate()
+        .map(|(i, cumulative_return)| (i as f64, cumulative_return))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn norm_ppf_inverts_norm_cdf() {
+        for p in [0.001, 0.025, 0.1, 0.5, 0.9, 0.975, 0.999] {
+            assert!((norm_cdf(norm_ppf(p)) - p).abs() < 1e-6, "p = {p}");
+        }
+        for x in [-3.0, -1.0, 0.0, 0.5, 2.0] {
+            assert!((norm_ppf(norm_cdf(x)) - x).abs() < 1e-4, "x = {x}");
+        }
+        assert!((norm_ppf(0.975) - 1.959_964).abs() < 1e-6);
+    }
+
+    #[test]
+    fn normal_prior_shrinks_by_precision() {
+        let prior = Prior::Normal {
+            mean: 0.0,
+            std: 1.0,
+        };
+        assert!((prior.posterior_mean(2.0, 1.0) - 1.0).abs() < 1e-12);
+        // prior precision 4 against sample precision 1
+        let prior = Prior::Normal {
+            mean: 1.0,
+            std: 0.5,
+        };
+        assert!((prior.posterior_mean(3.0, 1.0) - 1.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn uniform_prior_truncates_the_likelihood() {
+        let wide = Prior::Uniform {
+            min: -100.0,
+            max: 100.0,
+        };
+        assert!((wide.posterior_mean(0.7, 1.0) - 0.7).abs() < 1e-9);
+        // half-normal mean sqrt(2 / pi)
+        let half = Prior::Uniform {
+            min: 0.0,
+            max: 100.0,
+        };
+        assert!((half.posterior_mean(0.0, 1.0) - 0.797_885).abs() < 1e-5);
+        // no mass left inside the range, so fall back to the nearest bound
+        let narrow = Prior::Uniform { min: 0.0, max: 1.0 };
+        assert_eq!(narrow.posterior_mean(50.0, 0.1), 1.0);
+    }
+
+    #[test]
+    fn empirical_prior_weights_by_likelihood() {
+        let prior = Prior::Empirical(vec![0.0, 1.0]);
+        assert!((prior.posterior_mean(0.5, 1.0) - 0.5).abs() < 1e-12);
+        let prior = Prior::Empirical(vec![0.0, 2.0]);
+        let expected = 2.0 / (1.0 + (-2.0_f64).exp());
+        assert!((prior.posterior_mean(2.0, 1.0) - expected).abs() < 1e-12);
+        // every likelihood underflows without the shift
+        let prior = Prior::Empirical(vec![0.0, 100.0]);
+        assert!((prior.posterior_mean(1_000.0, 0.01) - 100.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn expected_max_sharpe_grows_with_trials() {
+        assert!((expected_max_sharpe(100, 1.0) - 2.53).abs() < 0.01);
+        assert!(
+            (expected_max_sharpe(100, 0.5) - 0.5 * expected_max_sharpe(100, 1.0)).abs() < 1e-12
+        );
+        assert!(expected_max_sharpe(1_000, 1.0) > expected_max_sharpe(100, 1.0));
+        assert_eq!(expected_max_sharpe(1, 1.0), 0.0);
+    }
+
+    #[test]
+    fn probabilistic_sharpe_is_even_at_the_benchmark() {
+        let config = SimConfig {
+            prior: Prior::Point(1.0),
+            model: ReturnModel::Normal,
+            periods: 253,
+            frequency: Frequency::Daily,
+            trials: 1,
+            benchmark_sharpe: 0.0,
+        };
+        let (_, mut stats) = gen_random_dist(&config, &mut ChaCha20Rng::seed_from_u64(7));
+        let periods_per_year = config.frequency.periods_per_year();
+        let psr = probabilistic_sharpe(&stats, stats.sample_sharpe, 253, periods_per_year);
+        // norm_cdf is only accurate to about 1e-7
+        assert!((psr - 0.5).abs() < 1e-6);
+
+        // Gaussian returns with an annual Sharpe of 1 over one year of days
+        (stats.sample_sharpe, stats.skew, stats.excess_kurtosis) = (1.0, 0.0, 0.0);
+        let expected = norm_cdf((1.0 + 0.5 / periods_per_year).sqrt().recip());
+        let psr = probabilistic_sharpe(&stats, 0.0, 253, periods_per_year);
+        assert!((psr - expected).abs() < 1e-12);
+        assert!(probabilistic_sharpe(&stats, 2.0, 253, periods_per_year) < 0.5);
+    }
+
+    #[test]
+    fn calc_drawdown_measures_duration_and_recovery() {
+        let recovered = calc_drawdown(&[0.0, 1.0, 0.5, -0.5, 0.2, 1.2, 0.8]);
+        assert_eq!(recovered.max_drawdown, 1.5);
+        assert_eq!((recovered.peak, recovered.trough), (Some(1), 3));
+        assert_eq!(recovered.recovery, Some(2));
+        assert_eq!(recovered.duration, 4);
+
+        let underwater = calc_drawdown(&[0.0, 1.0, 0.2, 0.4]);
+        assert!((underwater.max_drawdown - 0.8).abs() < 1e-12);
+        assert_eq!((underwater.peak, underwater.trough), (Some(1), 2));
+        assert_eq!(underwater.recovery, None);
+        assert_eq!(underwater.duration, 3);
+
+        // falling from the start, the peak is the level before the first return
+        let from_start = calc_drawdown(&[-0.5, -1.0, 0.3, 0.6]);
+        assert_eq!(from_start.max_drawdown, 1.0);
+        assert_eq!((from_start.peak, from_start.trough), (None, 1));
+        assert_eq!(from_start.recovery, Some(1));
+        assert_eq!(from_start.duration, 3);
+    }
 }
@@ -11,6 +11,8 @@ pub enum AppEvent {
     Enter,
     NextRound,
     ToggleTarget,
+    ToggleHistogram,
+    ToggleLeaderboard,
 }
 
 pub struct EventHandler {
@@ -32,6 +34,8 @@ impl EventHandler {
                         KeyCode::Char('r') => Ok(AppEvent::Recalc),
                         KeyCode::Char('n') => Ok(AppEvent::NextRound),
                         KeyCode::Char('t') => Ok(AppEvent::ToggleTarget),
+                        KeyCode::Char('h') => Ok(AppEvent::ToggleHistogram),
+                        KeyCode::Char('l') => Ok(AppEvent::ToggleLeaderboard),
                         KeyCode::Char(c) => Ok(AppEvent::CharInput(c)),
                         KeyCode::Backspace => Ok(AppEvent::Backspace),
                         KeyCode::Enter => Ok(AppEvent::Enter),
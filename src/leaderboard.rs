@@ -0,0 +1,264 @@
+use crate::app::{Difficulty, Guess};
+use clap::ValueEnum;
+use compact_str::{CompactString, format_compact};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const HEADER: &str = "date\tname\tdifficulty\trounds\thits\tpoints\tmae\tmean_z\tbias\tsettings";
+pub const TOP_N: usize = 10;
+
+/// A completed session as stored in the leaderboard file, one tab separated row each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub date: CompactString,
+    pub name: CompactString,
+    pub difficulty: Difficulty,
+    pub rounds: usize,
+    pub hits: usize,
+    pub points: f64,
+    pub mean_abs_error: f64,
+    pub mean_standardized_error: f64,
+    pub bias: f64,
+    /// Model, horizon, tolerance and scoring the session was played with.
+    pub settings: CompactString,
+}
+
+impl Entry {
+    /// Summarizes a session, or `None` if no guesses were made.
+    pub fn from_session(guess: &Guess) -> Option<Self> {
+        let card = guess.scorecard()?;
+        Some(Self {
+            date: format_date(utc_days()),
+            name: clean(&guess.player),
+            difficulty: guess.difficulty,
+            rounds: card.rounds,
+            hits: card.hits,
+            points: card.points,
+            mean_abs_error: card.mean_abs_error,
+            mean_standardized_error: card.mean_standardized_error,
+            bias: card.bias,
+            settings: clean(&guess.settings),
+        })
+    }
+
+    /// Mean points per round, so sessions of different lengths compare fairly.
+    pub fn points_per_round(&self) -> f64 {
+        self.points / self.rounds.max(1) as f64
+    }
+
+    fn to_row(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{:.2}\t{:.6}\t{:.6}\t{:.6}\t{}",
+            self.date,
+            self.name,
+            self.difficulty.name(),
+            self.rounds,
+            self.hits,
+            self.points,
+            self.mean_abs_error,
+            self.mean_standardized_error,
+            self.bias,
+            self.settings
+        )
+    }
+
+    fn from_row(row: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let columns: Vec<&str> = row.split('\t').collect();
+        let [
+            date,
+            name,
+            difficulty,
+            rounds,
+            hits,
+            points,
+            mae,
+            mean_z,
+            bias,
+            settings,
+        ] = columns[..]
+        else {
+            return Err(format!("expected 10 columns, found {}", columns.len()).into());
+        };
+        Ok(Self {
+            date: date.into(),
+            name: name.into(),
+            difficulty: Difficulty::from_str(difficulty, true)?,
+            rounds: rounds.parse()?,
+            hits: hits.parse()?,
+            points: points.parse()?,
+            mean_abs_error: mae.parse()?,
+            mean_standardized_error: mean_z.parse()?,
+            bias: bias.parse()?,
+            settings: settings.into(),
+        })
+    }
+}
+
+/// Tabs and newlines would break the row format.
+fn clean(s: &str) -> CompactString {
+    s.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+/// `$XDG_DATA_HOME/guess_the_sharpe/leaderboard.tsv`, falling back to `~/.local/share`.
+pub fn default_path() -> Option<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+        })?;
+    Some(data_home.join("guess_the_sharpe").join("leaderboard.tsv"))
+}
+
+/// Reads every stored session, treating a missing file as an empty leaderboard.
+pub fn load(path: &Path) -> Result<Vec<Entry>, Box<dyn std::error::Error>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && *line != HEADER)
+        .map(|(i, line)| Entry::from_row(line).map_err(|e| format!("line {}: {e}", i + 1).into()))
+        .collect()
+}
+
+pub fn append(path: &Path, entry: &Entry) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{HEADER}")?;
+    }
+    writeln!(file, "{}", entry.to_row())
+}
+
+/// Best `n` sessions at a difficulty, by points per round and then by mean standardized error.
+pub fn top(entries: &[Entry], difficulty: Difficulty, n: usize) -> Vec<&Entry> {
+    let mut top: Vec<&Entry> = entries
+        .iter()
+        .filter(|entry| entry.difficulty == difficulty)
+        .collect();
+    top.sort_by(|a, b| {
+        b.points_per_round().total_cmp(&a.points_per_round()).then(
+            a.mean_standardized_error
+                .total_cmp(&b.mean_standardized_error),
+        )
+    });
+    top.truncate(n);
+    top
+}
+
+/// Plain text table of the top sessions per difficulty, shared by the CLI and the TUI.
+pub fn format_table(entries: &[Entry], n: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for difficulty in Difficulty::value_variants() {
+        let top = top(entries, *difficulty, n);
+        if top.is_empty() {
+            continue;
+        }
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(difficulty.name().to_uppercase());
+        lines.push(format!(
+            "{:>3}  {:<16} {:<10} {:>6} {:>6} {:>8} {:>6} {:>8} {:>7} {:>8}  settings",
+            "#", "name", "date", "rounds", "hits", "points", "pts/rd", "mae", "mean z", "bias"
+        ));
+        for (rank, entry) in top.iter().enumerate() {
+            lines.push(format!(
+                "{:>3}  {:<16} {:<10} {:>6} {:>6} {:>8.1} {:>6.1} {:>8.4} {:>7.2} {:>+8.4}  {}",
+                rank + 1,
+                entry.name,
+                entry.date,
+                entry.rounds,
+                entry.hits,
+                entry.points,
+                entry.points_per_round(),
+                entry.mean_abs_error,
+                entry.mean_standardized_error,
+                entry.bias,
+                entry.settings
+            ));
+        }
+    }
+    if lines.is_empty() {
+        lines.push("No completed sessions yet".into());
+    }
+    lines
+}
+
+/// Whole days since the Unix epoch in UTC.
+pub fn utc_days() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / 86_400)
+}
+
+/// Formats days since the Unix epoch as `YYYY-MM-DD`, using Howard Hinnant's
+/// `civil_from_days` algorithm for the proleptic Gregorian calendar.
+pub fn format_date(days: u64) -> CompactString {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format_compact!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_date_known_days() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(10_956), "1999-12-31");
+        assert_eq!(format_date(11_016), "2000-02-29");
+        assert_eq!(format_date(20_742), "2026-10-16");
+    }
+
+    fn entry(rounds: usize, points: f64) -> Entry {
+        Entry {
+            date: "2026-10-16".into(),
+            name: "alice".into(),
+            difficulty: Difficulty::Hard,
+            rounds,
+            hits: 7,
+            points,
+            mean_abs_error: 0.123_456,
+            mean_standardized_error: 0.654_321,
+            bias: -0.012_5,
+            settings: "student-t, 252 days, iid errors, tolerance 0.08, gaussian scoring".into(),
+        }
+    }
+
+    #[test]
+    fn entry_row_round_trip() {
+        let entry = entry(10, 712.25);
+        assert_eq!(Entry::from_row(&entry.to_row()).unwrap(), entry);
+        assert!(Entry::from_row("2026-10-16\talice").is_err());
+    }
+
+    #[test]
+    fn top_ranks_by_points_per_round() {
+        let entries = [entry(60, 3_000.0), entry(10, 700.0), entry(5, 400.0)];
+        let ranked: Vec<usize> = top(&entries, Difficulty::Hard, TOP_N)
+            .iter()
+            .map(|entry| entry.rounds)
+            .collect();
+        assert_eq!(ranked, [5, 10, 60]);
+        assert!(top(&entries, Difficulty::Easy, TOP_N).is_empty());
+    }
+}
@@ -1,19 +1,28 @@
 mod app;
+mod data;
 mod dist;
 mod event;
+mod leaderboard;
 mod ui;
 
-use app::{App, AppMode, Guess};
-use clap::Parser;
-use compact_str::CompactString;
+use app::{App, AppMode, Difficulty, Guess, Scoring};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, ValueEnum, error::ErrorKind};
+use compact_str::{CompactString, format_compact};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use data::History;
+use dist::{ErrorModel, Frequency, MIN_PERIODS, Prior, ReturnModel, SimConfig};
 use event::{AppEvent, EventHandler};
 use ratatui::{Terminal, backend::CrosstermBackend};
+use std::fs;
 use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Instant;
 
 #[derive(Parser)]
 #[command(name = "guess_the_sharpe")]
@@ -22,10 +31,443 @@ struct Cli {
     /// Enable guessing mode
     #[arg(short = 'g', long = "guess")]
     guessing_mode: bool,
+
+    /// Play today's challenge: the same 10 rounds for everyone on the same UTC date
+    #[arg(
+        long = "daily",
+        conflicts_with_all = [
+            "rounds", "difficulty", "tolerance", "scoring", "frequency", "periods", "years",
+            "prior", "prior_min", "prior_max", "prior_mean", "prior_std", "prior_file",
+            "data", "best_of", "model", "error_model",
+        ]
+    )]
+    daily: bool,
+
+    /// Print the top sessions per difficulty from the leaderboard and exit
+    #[arg(long = "leaderboard")]
+    leaderboard: bool,
+
+    /// Player name recorded on the leaderboard [default: $USER]
+    #[arg(long = "name")]
+    name: Option<String>,
+
+    /// Preset tolerance, prior range, horizon and return model
+    #[arg(long = "difficulty", value_enum, default_value_t = Difficulty::Normal)]
+    difficulty: Difficulty,
+
+    /// Hit tolerance in standard errors of the target, overriding the difficulty preset
+    #[arg(long = "tolerance", value_parser = parse_tolerance)]
+    tolerance: Option<f64>,
+
+    /// Play a session of N rounds ending with a scorecard
+    #[arg(long = "rounds", value_name = "N", requires = "guessing_mode", value_parser = clap::value_parser!(u16).range(1..))]
+    rounds: Option<u16>,
+
+    /// Sharpe standard error used for the guessing tolerance
+    #[arg(short = 'e', long = "error-model", value_enum, default_value_t = ErrorModel::Iid)]
+    error_model: ErrorModel,
+
+    /// How points decay with the standardized error of a guess
+    #[arg(short = 's', long = "scoring", value_enum, default_value_t = Scoring::Gaussian)]
+    scoring: Scoring,
+
+    /// Sampling frequency of the returns
+    #[arg(short = 'f', long = "frequency", value_enum, default_value_t = Frequency::Daily)]
+    frequency: Frequency,
+
+    /// Number of returns in each track record (trading days for daily data) [default: set by --difficulty]
+    #[arg(short = 'd', long = "days", visible_alias = "periods", value_name = "N", value_parser = parse_periods)]
+    periods: Option<usize>,
+
+    /// Length of each track record in years, instead of --days
+    #[arg(short = 'y', long = "years", conflicts_with = "periods")]
+    years: Option<f64>,
+
+    /// Prior distribution of the true Sharpe ratio [default: uniform, or normal with --best-of]
+    #[arg(short = 'p', long = "prior", value_enum)]
+    prior: Option<PriorArg>,
+
+    /// Lower bound of the uniform prior [default: set by --difficulty]
+    #[arg(long = "prior-min", allow_negative_numbers = true)]
+    prior_min: Option<f64>,
+
+    /// Upper bound of the uniform prior [default: set by --difficulty]
+    #[arg(long = "prior-max", allow_negative_numbers = true)]
+    prior_max: Option<f64>,
+
+    /// Mean of the normal prior, or the value of the point prior
+    #[arg(
+        long = "prior-mean",
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    prior_mean: f64,
+
+    /// Standard deviation of the normal prior [default: 1, or 0.5 with --best-of]
+    #[arg(long = "prior-std")]
+    prior_std: Option<f64>,
+
+    /// File of whitespace or comma separated Sharpe ratios for the empirical prior
+    #[arg(long = "prior-file", required_if_eq("prior", "empirical"))]
+    prior_file: Option<PathBuf>,
+
+    /// CSV of `date,return` rows to draw real track records from instead of simulating
+    #[arg(
+        long = "data",
+        value_name = "FILE",
+        required_if_eq("model", "bootstrap")
+    )]
+    data: Option<PathBuf>,
+
+    /// Treat the values in --data as price levels rather than returns
+    #[arg(long = "prices", requires = "data")]
+    prices: bool,
+
+    /// Mean block length of the bootstrap model, in periods
+    #[arg(long = "block-length", default_value_t = 20.0)]
+    block_length: f64,
+
+    /// Demean and rescale bootstrapped returns to a true Sharpe drawn from the prior
+    #[arg(long = "redrift")]
+    redrift: bool,
+
+    /// Simulate N backtests per round and show only the one with the highest sample Sharpe
+    #[arg(short = 'b', long = "best-of", value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    best_of: u16,
+
+    /// Benchmark Sharpe for the probabilistic Sharpe ratio
+    #[arg(
+        long = "benchmark",
+        default_value_t = 0.0,
+        allow_negative_numbers = true
+    )]
+    benchmark: f64,
+
+    /// Process used to generate returns [default: set by --difficulty]
+    #[arg(short = 'm', long = "model", value_enum)]
+    model: Option<ModelArg>,
+
+    /// Degrees of freedom for the Student-t model (must be > 2)
+    #[arg(long = "dof", default_value_t = 5.0, value_parser = parse_dof)]
+    dof: f64,
+
+    /// GARCH(1,1) constant term
+    #[arg(long = "garch-omega", default_value_t = 0.05)]
+    garch_omega: f64,
+
+    /// GARCH(1,1) weight on the previous squared shock
+    #[arg(long = "garch-alpha", default_value_t = 0.1)]
+    garch_alpha: f64,
+
+    /// GARCH(1,1) weight on the previous variance
+    #[arg(long = "garch-beta", default_value_t = 0.85)]
+    garch_beta: f64,
+
+    /// Expected number of Sharpe regime switches per year in the regime-switching model
+    #[arg(long = "switch-rate", default_value_t = 0.5)]
+    switch_rate: f64,
+
+    /// Lag-one autocorrelation of returns in the AR(1) model
+    #[arg(long = "phi", default_value_t = 0.3, allow_negative_numbers = true)]
+    phi: f64,
+
+    /// Expected number of jumps per year in the jump-diffusion model
+    #[arg(long = "jump-intensity", default_value_t = 1.0)]
+    jump_intensity: f64,
+
+    /// Mean jump size, in per-period standard deviations
+    #[arg(long = "jump-mean", default_value_t = -6.0, allow_negative_numbers = true)]
+    jump_mean: f64,
+
+    /// Standard deviation of jump sizes, in per-period standard deviations
+    #[arg(long = "jump-std", default_value_t = 2.0)]
+    jump_std: f64,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PriorArg {
+    /// Uniform between --prior-min and --prior-max
+    Uniform,
+    /// Normal with --prior-mean and --prior-std
+    Normal,
+    /// Always --prior-mean
+    Point,
+    /// Drawn from the Sharpe ratios in --prior-file
+    Empirical,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ModelArg {
+    /// IID Gaussian returns
+    Normal,
+    /// Fat-tailed Student-t returns
+    StudentT,
+    /// GARCH(1,1) volatility clustering
+    Garch,
+    /// Markov regime-switching true Sharpe
+    Regime,
+    /// Autocorrelated AR(1) returns
+    Ar1,
+    /// Merton jump-diffusion with rare crashes
+    Jump,
+    /// Stationary block bootstrap of the --data series
+    Bootstrap,
+}
+
+impl Cli {
+    fn periods(&self) -> Result<usize, String> {
+        match (self.periods, self.years) {
+            (Some(periods), _) => Ok(periods),
+            (None, Some(years)) => {
+                parse_periods(&format!("{:.0}", years * self.frequency.periods_per_year()))
+            }
+            // the shorter presets are only a few months of monthly returns, so stretch them
+            (None, None) => {
+                let periods = self.difficulty.years() * self.frequency.periods_per_year();
+                Ok((periods.round() as usize).max(MIN_PERIODS))
+            }
+        }
+    }
+
+    fn prior(&self) -> Result<Prior, String> {
+        // selection bias is clearest when most backtests are close to worthless, but a point
+        // prior would make the true Sharpe the same every round
+        let (default, default_std) = if self.best_of > 1 {
+            (PriorArg::Normal, 0.5)
+        } else {
+            (PriorArg::Uniform, 1.0)
+        };
+        let std = self.prior_std.unwrap_or(default_std);
+        let (preset_min, preset_max) = self.difficulty.prior_range();
+        let (min, max) = (
+            self.prior_min.unwrap_or(preset_min),
+            self.prior_max.unwrap_or(preset_max),
+        );
+        match self.prior.unwrap_or(default) {
+            PriorArg::Uniform if min < max => Ok(Prior::Uniform { min, max }),
+            PriorArg::Uniform => Err("uniform prior needs --prior-min below --prior-max".into()),
+            PriorArg::Normal if std > 0.0 => Ok(Prior::Normal {
+                mean: self.prior_mean,
+                std,
+            }),
+            PriorArg::Normal => Err("normal prior needs a positive --prior-std".into()),
+            PriorArg::Point => Ok(Prior::Point(self.prior_mean)),
+            PriorArg::Empirical => {
+                let path = self.prior_file.as_deref().unwrap();
+                read_sharpes(path)
+                    .map(Prior::Empirical)
+                    .map_err(|e| format!("could not read prior file {}: {e}", path.display()))
+            }
+        }
+    }
+
+    fn player(&self) -> CompactString {
+        self.name
+            .clone()
+            .or_else(|| std::env::var("USER").ok())
+            .filter(|name| !name.trim().is_empty())
+            .map_or_else(|| "anonymous".into(), |name| name.trim().into())
+    }
+
+    /// Summary of the settings that change how hard a session is, e.g.
+    /// `normal, 504 days, tolerance 0.12, gaussian scoring`.
+    fn settings(&self, config: &SimConfig) -> CompactString {
+        let model = match config.model {
+            ReturnModel::Historical(_) => "historical".into(),
+            ReturnModel::Bootstrap { .. } => "bootstrap".into(),
+            _ => CompactString::from(self.model().to_possible_value().unwrap().get_name()),
+        };
+        format_compact!(
+            "{}, {} {}s, {} errors, tolerance {}, {} scoring",
+            model,
+            config.periods,
+            config.frequency.period_name().to_lowercase(),
+            self.error_model.to_possible_value().unwrap().get_name(),
+            self.tolerance(),
+            self.scoring.to_possible_value().unwrap().get_name()
+        )
+    }
+
+    fn tolerance(&self) -> f64 {
+        self.tolerance
+            .unwrap_or_else(|| self.difficulty.tolerance())
+    }
+
+    fn model(&self) -> ModelArg {
+        // real data is replayed as is unless a model is asked for
+        if self.data.is_some() {
+            return self.model.unwrap_or(ModelArg::Normal);
+        }
+        self.model.unwrap_or(match self.difficulty {
+            Difficulty::Easy | Difficulty::Normal => ModelArg::Normal,
+            Difficulty::Hard => ModelArg::StudentT,
+            Difficulty::Expert => ModelArg::Garch,
+        })
+    }
+
+    fn return_model(&self) -> Result<ReturnModel, String> {
+        if let Some(path) = &self.data {
+            let history = History::from_csv(path, self.prices)
+                .map(Rc::new)
+                .map_err(|e| format!("could not read data file {}: {e}", path.display()))?;
+            return match self.model() {
+                ModelArg::Normal => Ok(ReturnModel::Historical(history)),
+                ModelArg::Bootstrap if self.block_length >= 1.0 => Ok(ReturnModel::Bootstrap {
+                    history,
+                    mean_block: self.block_length,
+                    redrift: self.redrift,
+                }),
+                ModelArg::Bootstrap => Err("bootstrap block length must be at least 1".into()),
+                _ => Err("--data can only be combined with the bootstrap model".into()),
+            };
+        }
+
+        match self.model() {
+            ModelArg::Normal => Ok(ReturnModel::Normal),
+            ModelArg::StudentT => Ok(ReturnModel::StudentT { dof: self.dof }),
+            ModelArg::Garch => {
+                let (omega, alpha, beta) = (self.garch_omega, self.garch_alpha, self.garch_beta);
+                if omega <= 0.0 || alpha < 0.0 || beta < 0.0 {
+                    Err("GARCH omega must be positive and alpha, beta non-negative".into())
+                } else if alpha + beta >= 1.0 {
+                    Err("GARCH alpha + beta must be below 1 for a finite long-run variance".into())
+                } else {
+                    Ok(ReturnModel::Garch { omega, alpha, beta })
+                }
+            }
+            ModelArg::Regime => {
+                if self.switch_rate >= 0.0 {
+                    Ok(ReturnModel::RegimeSwitching {
+                        switch_rate: self.switch_rate,
+                    })
+                } else {
+                    Err("regime switch rate must be non-negative".into())
+                }
+            }
+            ModelArg::Ar1 => {
+                if self.phi.abs() < 1.0 {
+                    Ok(ReturnModel::Ar1 { phi: self.phi })
+                } else {
+                    Err("AR(1) coefficient must lie strictly between -1 and 1".into())
+                }
+            }
+            ModelArg::Bootstrap => Err("the bootstrap model needs --data".into()),
+            ModelArg::Jump => {
+                if self.jump_intensity <= 0.0 || self.jump_std < 0.0 {
+                    Err("jump intensity must be positive and jump std non-negative".into())
+                } else {
+                    Ok(ReturnModel::JumpDiffusion {
+                        intensity: self.jump_intensity,
+                        jump_mean: self.jump_mean,
+                        jump_std: self.jump_std,
+                    })
+                }
+            }
+        }
+    }
+}
+
+fn read_sharpes(path: &Path) -> Result<Vec<f64>, Box<dyn std::error::Error>> {
+    let sharpes = fs::read_to_string(path)?
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<f64>, _>>()?;
+    if sharpes.is_empty() {
+        return Err("no Sharpe ratios found".into());
+    }
+    Ok(sharpes)
+}
+
+fn parse_periods(s: &str) -> Result<usize, String> {
+    let periods: usize = s.parse().map_err(|e| format!("{e}"))?;
+    if periods >= MIN_PERIODS {
+        Ok(periods)
+    } else {
+        Err(format!(
+            "track record must contain at least {MIN_PERIODS} returns"
+        ))
+    }
+}
+
+fn parse_tolerance(s: &str) -> Result<f64, String> {
+    let tolerance: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    if tolerance > 0.0 {
+        Ok(tolerance)
+    } else {
+        Err("tolerance must be positive".into())
+    }
+}
+
+fn parse_dof(s: &str) -> Result<f64, String> {
+    let dof: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    if dof > 2.0 {
+        Ok(dof)
+    } else {
+        Err("degrees of freedom must be greater than 2 for finite variance".into())
+    }
+}
+
+/// Arguments that leave the game set by `--difficulty` unchanged.
+const PRESET_ARGS: [&str; 7] = [
+    "guessing_mode",
+    "daily",
+    "leaderboard",
+    "name",
+    "difficulty",
+    "rounds",
+    "benchmark",
+];
+
+const DAILY_ROUNDS: usize = 10;
+const DAILY_SALT: u64 = 0x5348_4152_5045_2121; // "SHARPE!!"
+
+fn print_leaderboard() -> Result<(), Box<dyn std::error::Error>> {
+    let path = leaderboard::default_path().ok_or("could not find a data directory")?;
+    let entries = leaderboard::load(&path)
+        .map_err(|e| format!("could not read leaderboard {}: {e}", path.display()))?;
+    for line in leaderboard::format_table(&entries, leaderboard::TOP_N) {
+        println!("{line}");
+    }
+    Ok(())
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    // only sessions played exactly as the difficulty preset are comparable on the leaderboard
+    let ranked = Cli::command()
+        .get_arguments()
+        .map(|arg| arg.get_id().as_str())
+        .all(|id| {
+            PRESET_ARGS.contains(&id) || matches.value_source(id) != Some(ValueSource::CommandLine)
+        });
+    if cli.leaderboard {
+        return print_leaderboard();
+    }
+    let config = cli
+        .return_model()
+        .and_then(|model| {
+            Ok(SimConfig {
+                prior: cli.prior()?,
+                model,
+                periods: cli.periods()?,
+                frequency: cli.frequency,
+                trials: usize::from(cli.best_of),
+                benchmark_sharpe: cli.benchmark,
+            })
+        })
+        .and_then(|config| match &config.model {
+            ReturnModel::Historical(history) if history.returns.len() < config.periods => {
+                Err(format!(
+                    "data file has {} returns, fewer than the {} needed per round",
+                    history.returns.len(),
+                    config.periods
+                ))
+            }
+            _ => Ok(config),
+        })
+        .unwrap_or_else(|e| Cli::command().error(ErrorKind::ValueValidation, e).exit());
 
     // Setup terminal
     enable_raw_mode()?;
@@ -35,19 +477,49 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run it
-    let mode = if cli.guessing_mode {
-        AppMode::Guessing(Guess {
+    let today = leaderboard::utc_days();
+    let daily = cli.daily.then(|| leaderboard::format_date(today));
+    let settings = match &daily {
+        Some(date) => format_compact!("daily {date}, {}", cli.settings(&config)),
+        None => cli.settings(&config),
+    };
+    let mode = if cli.guessing_mode || cli.daily {
+        AppMode::Guessing(Box::new(Guess {
             state: app::GuessState::WaitingForGuess,
-            target: app::GuessTarget::Sample,
+            // with several backtests the lesson is how far the winner's true Sharpe falls
+            target: if cli.best_of > 1 {
+                app::GuessTarget::Actual
+            } else {
+                app::GuessTarget::Sample
+            },
+            error_model: cli.error_model,
+            tolerance: cli.tolerance(),
             current_guess: CompactString::with_capacity(12),
             score: 0,
+            scoring: cli.scoring,
+            points: 0.0,
+            last_points: 0.0,
             last_guess: None,
             guess_was_correct: false,
-        })
+            rounds: if cli.daily {
+                Some(DAILY_ROUNDS)
+            } else {
+                cli.rounds.map(usize::from)
+            },
+            history: Vec::new(),
+            started: Instant::now(),
+            elapsed: None,
+            player: cli.player(),
+            difficulty: cli.difficulty,
+            settings,
+            daily,
+            ranked,
+        }))
     } else {
         AppMode::Display
     };
-    let mut app = App::new(mode);
+    let seed = cli.daily.then_some(DAILY_SALT ^ today);
+    let mut app = App::new(mode, config, seed);
     let event_handler = EventHandler::new();
     let res = run_app(&mut terminal, &mut app, &event_handler);
 
@@ -63,6 +535,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     if let Err(err) = res {
         println!("{:?}", err)
     }
+    if let Some(err) = app.leaderboard_error {
+        eprintln!("{err}");
+    }
+    if let AppMode::Guessing(guess) = &app.mode
+        && guess.state == app::GuessState::Finished
+        && let Some(summary) = guess.share_text()
+    {
+        println!("{summary}");
+    }
 
     Ok(())
 }
@@ -83,6 +564,8 @@ fn run_app<B: ratatui::backend::Backend>(
             AppEvent::Enter => app.submit_guess(),
             AppEvent::NextRound => app.next_round(),
             AppEvent::ToggleTarget => app.toggle_guess_target(),
+            AppEvent::ToggleHistogram => app.toggle_histogram(),
+            AppEvent::ToggleLeaderboard => app.toggle_leaderboard(),
             AppEvent::Tick => {
                 // Just update the display
             }
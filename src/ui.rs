@@ -1,12 +1,15 @@
-use crate::app::{App, AppMode, Guess, GuessState};
-use crate::dist::Stats;
+use crate::app::{App, AppMode, Guess, GuessState, GuessTarget, RoundResult};
+use crate::dist::{FAN_SIMS, ReturnModel, SimConfig, Stats, norm_pdf};
+use crate::leaderboard::{self, TOP_N};
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     symbols,
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType, Paragraph,
+    },
 };
 
 pub fn ui(f: &mut Frame, app: &App) {
@@ -14,7 +17,7 @@ pub fn ui(f: &mut Frame, app: &App) {
         .direction(Direction::Vertical)
         .margin(2)
         .constraints([
-            Constraint::Length(3), // Stats section
+            Constraint::Length(6), // Stats section
             Constraint::Min(0),    // Chart section
             Constraint::Length(3), // Instructions section
         ])
@@ -22,32 +25,224 @@ pub fn ui(f: &mut Frame, app: &App) {
 
     // Stats section
     match app.mode {
-        AppMode::Display => render_display_stats(f, &app.stats, chunks[0]),
-        AppMode::Guessing(ref guess) => render_guessing_stats(f, guess, &app.stats, chunks[0]),
+        AppMode::Display => render_display_stats(f, &app.stats, &app.config, chunks[0]),
+        AppMode::Guessing(ref guess) => {
+            render_guessing_stats(f, guess, &app.stats, &app.config, chunks[0])
+        }
     }
 
     // Chart section
-    render_chart(f, &app.plot_data, chunks[1]);
+    match app.mode {
+        _ if app.show_leaderboard => render_leaderboard(f, app, chunks[1]),
+        AppMode::Guessing(ref guess) if guess.state == GuessState::Finished => {
+            render_scorecard(f, guess, chunks[1])
+        }
+        _ => render_charts(f, app, chunks[1]),
+    }
 
     // Instructions section
     match app.mode {
         AppMode::Display => render_display_instructions(f, chunks[2]),
-        AppMode::Guessing(Guess { state, .. }) => render_guessing_instructions(f, state, chunks[2]),
+        AppMode::Guessing(ref guess) => render_guessing_instructions(f, guess, chunks[2]),
+    }
+}
+
+fn render_charts(f: &mut Frame, app: &App, area: Rect) {
+    let revealed = match app.mode {
+        AppMode::Display => true,
+        AppMode::Guessing(ref guess) => guess.state == GuessState::ShowingResult,
+    };
+    let chart_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(50), // Cumulative returns
+            Constraint::Percentage(25), // Drawdown
+            Constraint::Percentage(25), // Rolling Sharpe
+        ])
+        .split(area);
+    let reveal_guess = match app.mode {
+        AppMode::Guessing(ref guess) if guess.state == GuessState::ShowingResult => Some(guess),
+        _ => None,
+    };
+    let side_panels = usize::from(app.show_histogram) + usize::from(reveal_guess.is_some());
+    if side_panels > 0 {
+        let top_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chart_chunks[0]);
+        let side_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Fill(1); side_panels])
+            .split(top_chunks[1]);
+        render_chart(f, app, revealed, top_chunks[0]);
+        if app.show_histogram {
+            render_histogram(f, app, side_chunks[0]);
+        }
+        if let Some(guess) = reveal_guess {
+            render_sampling_distribution(f, app, guess, side_chunks[side_panels - 1]);
+        }
+    } else {
+        render_chart(f, app, revealed, chart_chunks[0]);
+    }
+    render_underwater(f, app, revealed, chart_chunks[1]);
+    render_rolling_sharpe(f, app, revealed, chart_chunks[2]);
+}
+
+/// Describes the true Sharpe of each regime and the day it started, e.g. `1.20 → @312 -0.40`.
+/// Empty when the whole series shares a single regime.
+fn regime_spans(stats: &Stats) -> Vec<Span<'static>> {
+    if stats.regimes.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut spans = vec![
+        Span::raw("  "),
+        Span::styled("Regimes: ", Style::default().fg(Color::Yellow)),
+    ];
+    for (i, regime) in stats.regimes.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(
+                format!(" → @{} ", regime.start),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        spans.push(Span::styled(
+            format!("{:.2}", regime.sharpe),
+            Style::default().fg(Color::LightGreen),
+        ));
     }
+    spans
 }
 
-fn render_display_stats(f: &mut Frame, stats: &Stats, area: ratatui::layout::Rect) {
+/// Posterior mean of the true Sharpe, e.g. `Posterior: 0.6553`. Empty for historical data,
+/// where the reference is the full-sample Sharpe and the prior plays no part.
+fn posterior_spans(
+    stats: &Stats,
+    config: &SimConfig,
+    separator: &'static str,
+    color: Color,
+) -> Vec<Span<'static>> {
+    if config.reference_name() != "Actual" {
+        return Vec::new();
+    }
+    vec![
+        Span::raw(separator),
+        Span::styled("Posterior: ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{:.4}", stats.posterior_sharpe),
+            Style::default().fg(color),
+        ),
+    ]
+}
+
+/// Max drawdown with its length and recovery time in periods, e.g.
+/// `Max DD: 0.3120 (len 212, recovery 80)`.
+fn drawdown_spans(stats: &Stats) -> Vec<Span<'static>> {
+    let drawdown = stats.drawdown;
+    let recovery = match drawdown.recovery {
+        Some(recovery) => recovery.to_string(),
+        None => "none".into(),
+    };
+    vec![
+        Span::raw("  "),
+        Span::styled("Max DD: ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{:.4}", drawdown.max_drawdown),
+            Style::default().fg(Color::Red),
+        ),
+        Span::styled(
+            format!(" (len {}, recovery {})", drawdown.duration, recovery),
+            Style::default().fg(Color::Gray),
+        ),
+    ]
+}
+
+/// Risk-adjusted metrics beyond the Sharpe ratio, e.g. `Vol: 1.02  Sortino: 1.45 ...`.
+fn metric_spans(stats: &Stats) -> Vec<Span<'static>> {
+    let metrics = stats.metrics;
+    let mut spans = Vec::new();
+    for (i, (label, value)) in [
+        ("Vol: ", metrics.volatility),
+        ("Sortino: ", metrics.sortino),
+        ("Calmar: ", metrics.calmar),
+        ("Omega: ", metrics.omega),
+        ("Tail Ratio: ", metrics.tail_ratio),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if i > 0 {
+            spans.push(Span::raw("  "));
+        }
+        spans.push(Span::styled(label, Style::default().fg(Color::Yellow)));
+        spans.push(Span::styled(
+            format!("{:.4}", value),
+            Style::default().fg(Color::White),
+        ));
+    }
+    spans
+}
+
+/// Probabilistic and deflated Sharpe ratios, e.g. `PSR(>0.00): 0.91  DSR: 0.12`, followed by
+/// the expected best Sharpe of worthless strategies when several backtests were run.
+fn confidence_spans(stats: &Stats, config: &SimConfig) -> Vec<Span<'static>> {
+    let mut spans = vec![
+        Span::styled(
+            format!("PSR(>{:.2}): ", config.benchmark_sharpe),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled(
+            format!("{:.2}", stats.probabilistic_sharpe),
+            Style::default().fg(Color::LightRed),
+        ),
+        Span::raw("  "),
+        Span::styled("DSR: ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{:.2}", stats.deflated_sharpe),
+            Style::default().fg(Color::LightRed),
+        ),
+    ];
+    let Some(selection) = stats.selection else {
+        return spans;
+    };
+
+    spans.extend([
+        Span::raw("  "),
+        Span::styled(
+            format!("E[max SR | {} trials]: ", selection.trials),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled(
+            format!("{:.2}", selection.expected_max_sharpe),
+            Style::default().fg(Color::LightRed),
+        ),
+    ]);
+    spans
+}
+
+fn render_display_stats(
+    f: &mut Frame,
+    stats: &Stats,
+    config: &SimConfig,
+    area: ratatui::layout::Rect,
+) {
     let sharpe_error = stats.sharpe_error;
     let mean_return = stats.sample_mean;
     let min_return = stats.sample_min;
     let max_return = stats.sample_max;
 
-    let stats_text = vec![Line::from(vec![
-        Span::styled("Actual Sharpe: ", Style::default().fg(Color::Yellow)),
+    let mut sharpe_spans = vec![
+        Span::styled(
+            format!("{} Sharpe: ", config.reference_name()),
+            Style::default().fg(Color::Yellow),
+        ),
         Span::styled(
             format!("{:.4}", stats.acc_sharpe),
             Style::default().fg(Color::Green),
         ),
+    ];
+    sharpe_spans.extend(posterior_spans(stats, config, "  ", Color::LightGreen));
+    sharpe_spans.extend([
         Span::raw("  "),
         Span::styled("Sample Sharpe: ", Style::default().fg(Color::Yellow)),
         Span::styled(
@@ -58,7 +253,16 @@ fn render_display_stats(f: &mut Frame, stats: &Stats, area: ratatui::layout::Rec
             format!(" ±{:.4}", sharpe_error),
             Style::default().fg(Color::Gray),
         ),
-        Span::raw("  "),
+        Span::styled(
+            format!(
+                " (Lo ±{:.4}, Mertens ±{:.4})",
+                stats.lo_sharpe_error, stats.mertens_sharpe_error
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+    ]);
+
+    let mut sample_spans = vec![
         Span::styled("Mean: ", Style::default().fg(Color::Yellow)),
         Span::styled(
             format!("{:.6}", mean_return),
@@ -76,7 +280,34 @@ fn render_display_stats(f: &mut Frame, stats: &Stats, area: ratatui::layout::Rec
             format!("{:.4}", max_return),
             Style::default().fg(Color::Green),
         ),
-    ])];
+        Span::raw("  "),
+        Span::styled("Skew: ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{:.2}", stats.skew),
+            Style::default().fg(Color::White),
+        ),
+        Span::raw("  "),
+        Span::styled("Ex Kurt: ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{:.2}", stats.excess_kurtosis),
+            Style::default().fg(Color::White),
+        ),
+        Span::raw("  "),
+        Span::styled("ρ1: ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{:.2}", stats.autocorr),
+            Style::default().fg(Color::White),
+        ),
+    ];
+    sample_spans.extend(drawdown_spans(stats));
+    let mut confidence = confidence_spans(stats, config);
+    confidence.extend(regime_spans(stats));
+    let stats_text = vec![
+        Line::from(sharpe_spans),
+        Line::from(sample_spans),
+        Line::from(metric_spans(stats)),
+        Line::from(confidence),
+    ];
 
     let stats_paragraph = Paragraph::new(stats_text)
         .block(Block::default().borders(Borders::ALL).title("Statistics"))
@@ -85,10 +316,21 @@ fn render_display_stats(f: &mut Frame, stats: &Stats, area: ratatui::layout::Rec
     f.render_widget(stats_paragraph, area);
 }
 
-fn render_guessing_stats(f: &mut Frame, guess: &Guess, stats: &Stats, area: ratatui::layout::Rect) {
+fn render_guessing_stats(
+    f: &mut Frame,
+    guess: &Guess,
+    stats: &Stats,
+    config: &SimConfig,
+    area: ratatui::layout::Rect,
+) {
+    let round = match guess.rounds {
+        Some(rounds) => format!("Round {}/{rounds}   ", guess.history.len() + 1),
+        None => String::new(),
+    };
     let stats_text = match guess.state {
         GuessState::WaitingForGuess => {
             vec![Line::from(vec![
+                Span::styled(round, Style::default().fg(Color::Gray)),
                 Span::styled("Your guess: ", Style::default().fg(Color::Yellow)),
                 Span::styled(
                     &guess.current_guess,
@@ -98,7 +340,7 @@ fn render_guessing_stats(f: &mut Frame, guess: &Guess, stats: &Stats, area: rata
                 ),
                 Span::raw("   "),
                 Span::styled(
-                    format!("Score: {}", guess.score),
+                    format!("Score: {}  Points: {:.0}", guess.score, guess.points),
                     Style::default()
                         .fg(Color::Green)
                         .add_modifier(Modifier::BOLD),
@@ -124,15 +366,12 @@ fn render_guessing_stats(f: &mut Frame, guess: &Guess, stats: &Stats, area: rata
             } else {
                 "INCORRECT"
             };
-            let sharpe_error = stats.sharpe_error;
+            let target_error = guess.target.error(stats, guess.error_model);
 
             // Get the target value that was being guessed
-            let target_value = match guess.target {
-                crate::app::GuessTarget::Sample => stats.sample_sharpe,
-                crate::app::GuessTarget::Actual => stats.acc_sharpe,
-            };
+            let target_value = guess.target.value(stats);
 
-            vec![Line::from(vec![
+            let result_spans = vec![
                 Span::styled("Guess: ", Style::default().fg(Color::Yellow)),
                 Span::styled(
                     format!("{:.4}", guess.last_guess.unwrap_or(0.0)),
@@ -145,7 +384,7 @@ fn render_guessing_stats(f: &mut Frame, guess: &Guess, stats: &Stats, area: rata
                     Style::default().fg(Color::Magenta),
                 ),
                 Span::styled(
-                    format!(" ({}) ±{:.4}", guess.target.name(), sharpe_error),
+                    format!(" ({}) ±{:.4}", guess.target.name(), target_error),
                     Style::default().fg(Color::Gray),
                 ),
                 Span::raw(" | "),
@@ -156,20 +395,61 @@ fn render_guessing_stats(f: &mut Frame, guess: &Guess, stats: &Stats, area: rata
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(" | "),
-                Span::styled("Actual: ", Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!("+{:.1} pts", guess.last_points),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" | "),
+                Span::styled(
+                    format!("Score: {}  Points: {:.0}", guess.score, guess.points),
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ];
+
+            let mut reveal_spans = vec![
+                Span::styled(
+                    format!("{}: ", config.reference_name()),
+                    Style::default().fg(Color::Yellow),
+                ),
                 Span::styled(
                     format!("{:.4}", stats.acc_sharpe),
                     Style::default().fg(Color::LightCyan),
                 ),
+            ];
+            reveal_spans.extend(posterior_spans(stats, config, " | ", Color::LightCyan));
+            reveal_spans.extend([
                 Span::raw(" | "),
                 Span::styled("Sample: ", Style::default().fg(Color::Yellow)),
                 Span::styled(
                     format!("{:.4}", stats.sample_sharpe),
                     Style::default().fg(Color::LightCyan),
                 ),
+            ]);
+            reveal_spans.extend(drawdown_spans(stats));
+            let mut confidence = confidence_spans(stats, config);
+            confidence.extend(regime_spans(stats));
+            vec![
+                Line::from(result_spans),
+                Line::from(reveal_spans),
+                Line::from(metric_spans(stats)),
+                Line::from(confidence),
+            ]
+        }
+        GuessState::Finished => {
+            vec![Line::from(vec![
+                Span::styled(
+                    "Session complete",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
                 Span::raw(" | "),
                 Span::styled(
-                    format!("Score: {}", guess.score),
+                    format!("Score: {}  Points: {:.0}", guess.score, guess.points),
                     Style::default()
                         .fg(Color::Green)
                         .add_modifier(Modifier::BOLD),
@@ -189,7 +469,172 @@ fn render_guessing_stats(f: &mut Frame, guess: &Guess, stats: &Stats, area: rata
     f.render_widget(stats_paragraph, area);
 }
 
-fn render_chart(f: &mut Frame, plot_data: &[(f64, f64)], area: ratatui::layout::Rect) {
+/// Describes a round as e.g. `#3 Sample: guessed 0.5000 vs 1.2345 (0.62σ, +82.5 pts)`.
+fn round_spans(index: usize, round: &RoundResult) -> Vec<Span<'static>> {
+    let (mark, color) = if round.correct {
+        ("✓", Color::Green)
+    } else {
+        ("✗", Color::Red)
+    };
+    vec![
+        Span::styled(
+            format!("#{:<3}", index + 1),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(
+            format!("{:<13}", round.target.name()),
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::raw(format!(
+            "guessed {:>8.4} vs {:>8.4}",
+            round.guess, round.value
+        )),
+        Span::styled(
+            format!(
+                "  ({:.2}σ, +{:.1} pts) ",
+                round.standardized_error(),
+                round.points
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(mark, Style::default().fg(color)),
+    ]
+}
+
+fn render_scorecard(f: &mut Frame, guess: &Guess, area: Rect) {
+    let Some(card) = guess.scorecard() else {
+        return;
+    };
+    let label = Style::default().fg(Color::Yellow);
+    let value = Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD);
+    let elapsed = guess.elapsed.unwrap_or_default().as_secs();
+    let bias = match card.bias {
+        b if b > 0.0 => "overestimating",
+        b if b < 0.0 => "underestimating",
+        _ => "unbiased",
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Rounds: ", label),
+            Span::styled(card.rounds.to_string(), value),
+            Span::raw("   "),
+            Span::styled("Hit rate: ", label),
+            Span::styled(
+                format!(
+                    "{}/{} ({:.0}%)",
+                    card.hits,
+                    card.rounds,
+                    100.0 * card.hits as f64 / card.rounds as f64
+                ),
+                value,
+            ),
+            Span::raw("   "),
+            Span::styled("Points: ", label),
+            Span::styled(format!("{:.0}", card.points), value),
+            Span::raw("   "),
+            Span::styled("Time: ", label),
+            Span::styled(format!("{}m {:02}s", elapsed / 60, elapsed % 60), value),
+        ]),
+        Line::from(vec![
+            Span::styled("Mean absolute error: ", label),
+            Span::styled(format!("{:.4}", card.mean_abs_error), value),
+            Span::raw("   "),
+            Span::styled("Mean standardized error: ", label),
+            Span::styled(format!("{:.2}σ", card.mean_standardized_error), value),
+            Span::raw("   "),
+            Span::styled("Bias: ", label),
+            Span::styled(format!("{:+.4} ({bias})", card.bias), value),
+        ]),
+        Line::from(""),
+    ];
+    for (name, index) in [("Best round:  ", card.best), ("Worst round: ", card.worst)] {
+        let mut spans = vec![Span::styled(name, label)];
+        spans.extend(round_spans(index, &guess.history[index]));
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.extend(
+        guess
+            .history
+            .iter()
+            .enumerate()
+            .map(|(i, round)| Line::from(round_spans(i, round))),
+    );
+    if let Some(summary) = guess.share_text() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Share your result (also printed on exit):",
+            label,
+        )));
+        lines.extend(summary.lines().map(|line| Line::from(line.to_owned())));
+    }
+    if !guess.ranked {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Not saved to the leaderboard: the settings differ from the difficulty preset",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+
+    let scorecard = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Scorecard"))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(scorecard, area);
+}
+
+fn render_leaderboard(f: &mut Frame, app: &App, area: Rect) {
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(err) = &app.leaderboard_error {
+        lines.push(Line::from(Span::styled(
+            err.clone(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines.extend(
+        leaderboard::format_table(&app.leaderboard, TOP_N)
+            .into_iter()
+            .map(Line::from),
+    );
+
+    let title = match &app.leaderboard_path {
+        Some(path) => format!("Leaderboard ({})", path.display()),
+        None => "Leaderboard".into(),
+    };
+    let table = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(table, area);
+}
+
+/// Title of the returns chart, naming the dates of a historical window once revealed.
+fn chart_title(app: &App, revealed: bool) -> Line<'static> {
+    let title = match (&app.config.model, app.stats.window_start) {
+        (ReturnModel::Historical(history), Some(start)) if revealed => format!(
+            "Cumulative Returns Plot ({} to {})",
+            history.dates[start],
+            history.dates[start + app.config.periods - 1]
+        ),
+        _ => "Cumulative Returns Plot".into(),
+    };
+
+    let mut spans = vec![Span::raw(title)];
+    if revealed && app.fan_chart.is_some() {
+        spans.push(Span::styled(
+            format!("  5-95% of {FAN_SIMS} paths"),
+            Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled("  Median", Style::default().fg(Color::Blue)));
+    }
+    Line::from(spans)
+}
+
+fn render_chart(f: &mut Frame, app: &App, revealed: bool, area: ratatui::layout::Rect) {
+    let plot_data = &app.plot_data;
     // Find min and max values for scaling
     let min_y = plot_data
         .iter()
@@ -201,23 +646,57 @@ fn render_chart(f: &mut Frame, plot_data: &[(f64, f64)], area: ratatui::layout::
         .fold(f64::NEG_INFINITY, f64::max);
     let max_x = plot_data.len() as f64;
 
-    let datasets = vec![
+    let fan_chart = app.fan_chart.as_ref().filter(|_| revealed);
+    // Bands go in first so the realized path is drawn over them
+    let mut datasets = Vec::new();
+    let (mut min_y, mut max_y) = (min_y, max_y);
+    if let Some(fan) = fan_chart {
+        for (band, color) in [
+            (&fan.lower, Color::DarkGray),
+            (&fan.upper, Color::DarkGray),
+            (&fan.median, Color::Blue),
+        ] {
+            for (_, y) in band {
+                (min_y, max_y) = (min_y.min(*y), max_y.max(*y));
+            }
+            datasets.push(
+                Dataset::default()
+                    .marker(symbols::Marker::Braille)
+                    .style(Style::default().fg(color))
+                    .graph_type(GraphType::Line)
+                    .data(band),
+            );
+        }
+    }
+    datasets.push(
         Dataset::default()
             .marker(symbols::Marker::Braille)
             .style(Style::default().fg(Color::Cyan))
             .graph_type(GraphType::Line)
             .data(plot_data),
-    ];
+    );
+    // Drawn over the curve to pick out the peak-to-trough of the worst drawdown, from the
+    // first point when the fall starts below the starting level
+    let drawdown = &app.stats.drawdown;
+    if drawdown.max_drawdown > 0.0 {
+        datasets.push(
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Red))
+                .graph_type(GraphType::Line)
+                .data(&plot_data[drawdown.peak.unwrap_or(0)..=drawdown.trough]),
+        );
+    }
 
     let chart = Chart::new(datasets)
         .block(
             Block::default()
-                .title("Cumulative Returns Plot")
+                .title(chart_title(app, revealed))
                 .borders(Borders::ALL),
         )
         .x_axis(
             Axis::default()
-                .title("Day")
+                .title(app.config.frequency.period_name())
                 .style(Style::default().fg(Color::Gray))
                 .bounds([0.0, max_x])
                 .labels(vec![
@@ -256,6 +735,347 @@ fn render_chart(f: &mut Frame, plot_data: &[(f64, f64)], area: ratatui::layout::
     f.render_widget(chart, area);
 }
 
+/// Normal approximation to the sample Sharpe's distribution around the true Sharpe,
+/// marking where the sample and the player's guess landed.
+fn render_sampling_distribution(f: &mut Frame, app: &App, guess: &Guess, area: Rect) {
+    const POINTS: usize = 200;
+
+    let stats = &app.stats;
+    let centre = stats.acc_sharpe;
+    let error = stats.error(guess.error_model);
+    let density = |x: f64| norm_pdf((x - centre) / error) / error;
+    let peak = density(centre);
+
+    // only Sharpe guesses live on this axis
+    let guess_value = guess
+        .last_guess
+        .filter(|_| matches!(guess.target, GuessTarget::Sample | GuessTarget::Actual));
+    let (min_x, max_x) = [stats.sample_sharpe].into_iter().chain(guess_value).fold(
+        (centre - 4.0 * error, centre + 4.0 * error),
+        |(lo, hi), x| (lo.min(x - 0.5 * error), hi.max(x + 0.5 * error)),
+    );
+
+    let curve: Vec<(f64, f64)> = (0..=POINTS)
+        .map(|i| {
+            let x = min_x + (max_x - min_x) * i as f64 / POINTS as f64;
+            (x, density(x))
+        })
+        .collect();
+    let band: Vec<(f64, f64)> = curve
+        .iter()
+        .copied()
+        .filter(|(x, _)| (x - centre).abs() <= error)
+        .collect();
+    let marker = |x: f64| [(x, 0.0), (x, peak * 1.05)];
+    let sample_line = marker(stats.sample_sharpe);
+    let guess_line = guess_value.map(marker);
+
+    let mut datasets = vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::DarkGray))
+            .graph_type(GraphType::Bar)
+            .data(&band),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Cyan))
+            .graph_type(GraphType::Line)
+            .data(&curve),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Green))
+            .graph_type(GraphType::Line)
+            .data(&sample_line),
+    ];
+    let mut title = vec![
+        Span::raw("Sampling Distribution"),
+        Span::styled(
+            format!("  ±{:.2}", error),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled("  Sample", Style::default().fg(Color::Green)),
+    ];
+    if let Some(guess_line) = &guess_line {
+        datasets.push(
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Yellow))
+                .graph_type(GraphType::Line)
+                .data(guess_line),
+        );
+        title.push(Span::styled("  Guess", Style::default().fg(Color::Yellow)));
+    }
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(Line::from(title))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .title("SR")
+                .style(Style::default().fg(Color::Gray))
+                .bounds([min_x, max_x])
+                .labels(vec![
+                    Span::styled(
+                        format!("{:.2}", min_x),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:.2}", min_x.midpoint(max_x)),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:.2}", max_x),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, peak * 1.05]),
+        );
+
+    f.render_widget(chart, area);
+}
+
+fn render_histogram(f: &mut Frame, app: &App, area: Rect) {
+    let histogram = &app.histogram;
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::raw(format!("{} Returns", app.config.frequency.period_name())),
+            Span::styled("  Normal fit", Style::default().fg(Color::Yellow)),
+            Span::raw(format!(
+                "  Skew: {:.2}  Ex Kurt: {:.2}",
+                app.stats.skew, app.stats.excess_kurtosis
+            )),
+        ]))
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if inner.width == 0 || inner.height < 2 {
+        return;
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    // Fit the bars to the panel, dropping the gaps between them if space is tight
+    let bins = histogram.counts.len() as u16;
+    let (bar_width, bar_gap) = if rows[0].width + 1 >= 2 * bins {
+        ((rows[0].width + 1) / bins - 1, 1)
+    } else {
+        ((rows[0].width / bins).max(1), 0)
+    };
+    let bars_area = Rect {
+        width: (bins * (bar_width + bar_gap)).min(rows[0].width),
+        ..rows[0]
+    };
+
+    let max_count = histogram.counts.iter().copied().max().unwrap_or(0) as f64;
+    let max_fit = histogram
+        .normal_fit
+        .iter()
+        .map(|(_, y)| *y)
+        .fold(0.0, f64::max);
+    let max_y = max_count.max(max_fit).ceil().max(1.0);
+
+    let bars: Vec<Bar> = histogram
+        .counts
+        .iter()
+        .map(|&count| {
+            Bar::default()
+                .value(count)
+                .text_value(String::new())
+                .style(Style::default().fg(Color::Cyan))
+        })
+        .collect();
+    let bar_chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(bar_gap)
+        .max(max_y as u64);
+    f.render_widget(bar_chart, bars_area);
+
+    // Chart only draws non-blank braille cells, so the density sits on top of the bars
+    let overlay = Chart::new(vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Yellow))
+            .graph_type(GraphType::Line)
+            .data(&histogram.normal_fit),
+    ])
+    .x_axis(Axis::default().bounds([0.0, f64::from(bins)]))
+    .y_axis(Axis::default().bounds([0.0, max_y]));
+    f.render_widget(overlay, bars_area);
+
+    let range_area = Rect {
+        width: bars_area.width,
+        ..rows[1]
+    };
+    let label_style = Style::default().add_modifier(Modifier::BOLD);
+    f.render_widget(
+        Paragraph::new(Span::styled(format!("{:.3}", histogram.min), label_style)),
+        range_area,
+    );
+    f.render_widget(
+        Paragraph::new(Span::styled(format!("{:.3}", histogram.max), label_style))
+            .alignment(Alignment::Right),
+        range_area,
+    );
+}
+
+fn render_underwater(f: &mut Frame, app: &App, revealed: bool, area: ratatui::layout::Rect) {
+    let max_x = app.plot_data.len() as f64;
+    let deepest = app.underwater.iter().map(|(_, y)| *y).fold(0.0, f64::min);
+    // Round the floor down to a 0.05 step so the axis label doesn't give away the max drawdown
+    let min_y = ((deepest / 0.05).floor() * 0.05).min(-0.05);
+
+    let mut title = vec![Span::raw("Drawdown (cumulative return)")];
+    if revealed {
+        title.push(Span::styled(
+            format!("  Max: {:.4}", app.stats.drawdown.max_drawdown),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    let datasets = vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Red))
+            .graph_type(GraphType::Line)
+            .data(&app.underwater),
+    ];
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(Line::from(title))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, max_x]),
+        )
+        .y_axis(
+            Axis::default()
+                .title("DD")
+                .style(Style::default().fg(Color::Gray))
+                .bounds([min_y, 0.0])
+                .labels(vec![
+                    Span::styled(
+                        format!("{:.2}", min_y),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:.2}", min_y / 2.0),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled("0", Style::default().add_modifier(Modifier::BOLD)),
+                ]),
+        );
+
+    f.render_widget(chart, area);
+}
+
+fn render_rolling_sharpe(f: &mut Frame, app: &App, revealed: bool, area: ratatui::layout::Rect) {
+    let max_x = app.plot_data.len() as f64;
+    let true_line = [(0.0, app.stats.acc_sharpe), (max_x, app.stats.acc_sharpe)];
+
+    let colors = [Color::Yellow, Color::Magenta];
+    // the panel is too short for a legend, so the title doubles as one
+    let mut title = vec![Span::raw("Rolling Sharpe Ratio")];
+    for ((window, _), color) in app.rolling_sharpe.iter().zip(colors) {
+        title.push(Span::styled(
+            format!("  {window}-{}", app.config.frequency.period_name()),
+            Style::default().fg(color),
+        ));
+    }
+    if revealed {
+        title.push(Span::styled(
+            format!("  {}", app.config.reference_name()),
+            Style::default().fg(Color::Green),
+        ));
+    }
+
+    let mut datasets: Vec<Dataset> = app
+        .rolling_sharpe
+        .iter()
+        .zip(colors)
+        .map(|((_, data), color)| {
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(color))
+                .graph_type(GraphType::Line)
+                .data(data)
+        })
+        .collect();
+    // the true Sharpe would give the answer away before the reveal
+    if revealed {
+        datasets.push(
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Green))
+                .graph_type(GraphType::Line)
+                .data(&true_line),
+        );
+    }
+
+    let values = app
+        .rolling_sharpe
+        .iter()
+        .flat_map(|(_, data)| data.iter().map(|(_, y)| *y))
+        .chain(revealed.then_some(app.stats.acc_sharpe));
+    let (min_y, max_y) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), y| {
+        (min.min(y), max.max(y))
+    });
+    // no window fits in a very short track record
+    let (min_y, max_y) = if min_y <= max_y {
+        (min_y, max_y)
+    } else {
+        (-1.0, 1.0)
+    };
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(Line::from(title))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, max_x]),
+        )
+        .y_axis(
+            Axis::default()
+                .title("Sharpe")
+                .style(Style::default().fg(Color::Gray))
+                .bounds([min_y, max_y])
+                .labels(vec![
+                    Span::styled(
+                        format!("{:.2}", min_y),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:.2}", min_y.midpoint(max_y)),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:.2}", max_y),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                ]),
+        );
+
+    f.render_widget(chart, area);
+}
+
 fn render_display_instructions(f: &mut Frame, area: ratatui::layout::Rect) {
     let instructions = vec![Line::from(vec![
         Span::styled("Press ", Style::default().fg(Color::White)),
@@ -266,6 +1086,20 @@ fn render_display_instructions(f: &mut Frame, area: ratatui::layout::Rect) {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(" to recalculate, ", Style::default().fg(Color::White)),
+        Span::styled(
+            "'h'",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" to toggle histogram, ", Style::default().fg(Color::White)),
+        Span::styled(
+            "'l'",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" for the leaderboard, ", Style::default().fg(Color::White)),
         Span::styled(
             "'q'",
             Style::default()
@@ -282,12 +1116,13 @@ fn render_display_instructions(f: &mut Frame, area: ratatui::layout::Rect) {
     f.render_widget(instructions_paragraph, area);
 }
 
-fn render_guessing_instructions(
-    f: &mut Frame,
-    guess_state: GuessState,
-    area: ratatui::layout::Rect,
-) {
-    let instructions = match guess_state {
+fn render_guessing_instructions(f: &mut Frame, guess: &Guess, area: ratatui::layout::Rect) {
+    let next_round = if guess.is_last_round() {
+        " to see your scorecard, "
+    } else {
+        " for next round, "
+    };
+    let instructions = match guess.state {
         GuessState::WaitingForGuess => {
             vec![Line::from(vec![
                 Span::styled(
@@ -308,6 +1143,13 @@ fn render_guessing_instructions(
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(" to toggle target, ", Style::default().fg(Color::White)),
+                Span::styled(
+                    "'h'",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" to toggle histogram, ", Style::default().fg(Color::White)),
                 Span::styled(
                     "'q'",
                     Style::default()
@@ -326,7 +1168,29 @@ fn render_guessing_instructions(
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(" for next round, ", Style::default().fg(Color::White)),
+                Span::styled(next_round, Style::default().fg(Color::White)),
+                Span::styled(
+                    "'q'",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" to quit", Style::default().fg(Color::White)),
+            ])]
+        }
+        GuessState::Finished => {
+            vec![Line::from(vec![
+                Span::styled(
+                    "Session complete. Press ",
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    "'l'",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" for the leaderboard, ", Style::default().fg(Color::White)),
                 Span::styled(
                     "'q'",
                     Style::default()